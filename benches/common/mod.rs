@@ -0,0 +1,37 @@
+// Bits shared by the benchmarks: reading the input size, generating input, and printing timings.
+
+use std::env;
+use std::fmt::Display;
+use std::time::Instant;
+
+
+// the first number on the command line, or the default (cargo bench passes a --bench flag of its
+// own, so anything that isn't a number is skipped)
+pub fn size(default: usize) -> usize {
+    env::args().skip(1).filter_map(|arg| arg.parse().ok()).next().unwrap_or(default)
+}
+
+
+// a simple linear congruential generator, so every run benchmarks the same input
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new() -> Random {
+        Random { state: 2017 }
+    }
+
+    pub fn next(&mut self) -> u64 {
+        self.state = self.state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        self.state
+    }
+}
+
+
+pub fn report<T: Display>(name: &str, start: Instant, answer: T) {
+    let elapsed = start.elapsed().as_secs_f64();
+    println!("{:<28} {:>8.1} ms  (answer {})", name, elapsed * 1000.0, answer);
+}
@@ -0,0 +1,69 @@
+// Compares the day 1 solvers that read the whole input into memory with the streaming ones.
+//
+//     cargo bench --bench day01 [-- DIGITS]
+//
+// The input is DIGITS pseudo-random digits (10 million by default), with a newline every 80.
+
+extern crate advent2017;
+
+mod common;
+
+use advent2017::Solution;
+use advent2017::day01::{self, Alphabet, Day01, Offset};
+use std::io::Cursor;
+use std::time::Instant;
+
+
+fn main() {
+    let length = common::size(10_000_000);
+    let input = random_digits(length);
+    println!("{} digits ({:.1} MB)", length, input.len() as f64 / 1e6);
+    let decimal = Alphabet::decimal();
+
+    let adjacent = time("part one, graphemes", || {
+        Day01::part_one(&Day01::parse(&input).unwrap()).unwrap()
+    });
+    time("part one, stream_captcha", || {
+        let sum = day01::stream_captcha(input.as_bytes(), 1, &decimal).unwrap();
+        assert_eq!(sum, adjacent);
+        sum
+    });
+
+    let opposite = time("part two, graphemes", || {
+        Day01::part_two(&Day01::parse(&input).unwrap()).unwrap()
+    });
+    time("part two, seek_captcha", || {
+        let sum = day01::seek_captcha(Cursor::new(&input), &Offset::HalfLength, &decimal);
+        let sum = sum.unwrap();
+        assert_eq!(sum, opposite);
+        sum
+    });
+}
+
+
+fn time<F: FnMut() -> u64>(name: &str, mut f: F) -> u64 {
+    let start = Instant::now();
+    let answer = f();
+    common::report(name, start, answer);
+    answer
+}
+
+
+// pseudo-random digits, repeating the previous digit half the time
+// so that there's plenty to match
+fn random_digits(length: usize) -> String {
+    let mut random = common::Random::new();
+    let mut digit = 0;
+    let mut input = String::with_capacity(length + length / 80 + 1);
+    for i in 0..length {
+        let state = random.next();
+        if state >> 63 == 0 {
+            digit = (state >> 40) % 10;
+        }
+        input.push((b'0' + digit as u8) as char);
+        if i % 80 == 79 {
+            input.push('\n');
+        }
+    }
+    input
+}
@@ -0,0 +1,73 @@
+// Compares the serial day 2 checksum (read everything, then sum) with the parallel streaming one.
+//
+//     cargo bench --bench day02 [-- ROWS]
+//
+// The spreadsheet has ROWS rows (a million by default) of 16 pseudo-random numbers.
+
+extern crate advent2017;
+
+mod common;
+
+use advent2017::day02::parallel::parallel_checksum;
+use advent2017::day02::{self, Difference, Quotient, RowChecksum, Separator};
+use std::thread;
+use std::time::Instant;
+
+
+fn main() {
+    let rows = common::size(1_000_000);
+    let input = random_spreadsheet(rows);
+    println!("{} rows ({:.1} MB)", rows, input.len() as f64 / 1e6);
+
+    let cores = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    compare("difference", &input, &Difference, cores);
+    compare("quotient", &input, &Quotient, cores);
+}
+
+
+fn compare<C: RowChecksum + Sync>(name: &str, input: &str, row_checksum: &C, cores: usize) {
+    let start = Instant::now();
+    let matrix = day02::parse_spreadsheet(input, Separator::Tab).unwrap();
+    let serial = i64::from(day02::matrix_checksum(&matrix, row_checksum).unwrap());
+    common::report(&format!("{}, serial", name), start, serial);
+
+    let mut threads = 1;
+    while threads <= cores {
+        let start = Instant::now();
+        let total = parallel_checksum(input.as_bytes(), None, row_checksum, threads).unwrap();
+        assert_eq!(total, serial);
+        common::report(&format!("{}, {} threads", name, threads), start, total);
+        threads *= 2;
+    }
+}
+
+
+// rows of pseudo-random numbers, each with exactly one pair where
+// one number divides another (a small number, and a multiple of it). The numbers are kept under
+// 1000 so the serial checksum still fits in an i32.
+fn random_spreadsheet(rows: usize) -> String {
+    let mut random = common::Random::new();
+    let mut next = |range: u64| (random.next() >> 33) % range;
+
+    let mut input = String::new();
+    for _ in 0..rows {
+        let divisor = 100 + next(100);
+        let mut row = vec![divisor];
+        // primes bigger than the divisor can't divide each other, the divisor, or its multiple
+        while row.len() < 15 {
+            let n = divisor + 1 + next(999 - divisor);
+            if is_prime(n) && !row.contains(&n) {
+                row.push(n);
+            }
+        }
+        row.push(divisor * (2 + next(3)));
+        let cells: Vec<String> = row.iter().map(ToString::to_string).collect();
+        input.push_str(&cells.join("\t"));
+        input.push('\n');
+    }
+    input
+}
+
+fn is_prime(n: u64) -> bool {
+    n > 1 && (2..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d))
+}
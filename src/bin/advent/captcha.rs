@@ -0,0 +1,42 @@
+// `advent captcha`: the day 1 captcha for any offset and any base, streamed so inputs of any size
+// will do.
+
+use advent2017::day01::{self, Alphabet, Offset};
+use std::fs::File;
+use std::io::{self, BufReader};
+
+use {Failure, Flags};
+
+
+pub fn main(flags: &Flags) -> Result<(), Failure> {
+    let offset = match flags.get("--offset") {
+        None => Offset::Fixed(1),
+        Some("half") => Offset::HalfLength,
+        Some(value) => Offset::Fixed(value.parse().map_err(|_| {
+            Failure::Usage(String::from("--offset must be a number or \"half\""))
+        })?),
+    };
+
+    let alphabet = match flags.number("--radix", 2, 36)? {
+        Some(radix) => Alphabet::radix(radix).unwrap(),
+        None => Alphabet::decimal(),
+    };
+
+    let sum = match (flags.get("--input"), offset) {
+        // stdin can only be read once, so the offset has to be known before reading it
+        (Some("-"), Offset::Fixed(k)) => day01::stream_captcha(io::stdin().lock(), k, &alphabet)?,
+        (Some("-"), _) => {
+            let message = "an offset that depends on the input's length needs a file, not stdin";
+            return Err(Failure::Usage(String::from(message)));
+        }
+        (path, offset) => {
+            let path = path.unwrap_or("data/day01.txt");
+            let file = File::open(path)
+                .map_err(|e| Failure::Solver(format!("could not read {}: {}", path, e)))?;
+            day01::seek_captcha(BufReader::new(file), &offset, &alphabet)?
+        }
+    };
+
+    println!("{}", sum);
+    Ok(())
+}
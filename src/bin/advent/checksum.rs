@@ -0,0 +1,111 @@
+// `advent checksum`: day 2 spreadsheet checksums for tab-, comma- or whitespace-separated input.
+
+use advent2017::day02::diagnostics;
+use advent2017::day02::parallel::parallel_checksum;
+use advent2017::day02::{self, Separator, STRATEGIES};
+use std::fs::File;
+use std::io::{self, BufReader, Write};
+
+use {read_input, written, Failure, Flags};
+
+
+pub fn main(flags: &Flags) -> Result<(), Failure> {
+    let output = flags.choice("--output", &["total", "table", "json"])?;
+    let separator = match flags.choice("--separator", &["auto", "tab", "comma", "whitespace"])? {
+        "tab" => Some(Separator::Tab),
+        "comma" => Some(Separator::Comma),
+        "whitespace" => Some(Separator::Whitespace),
+        _ => None,
+    };
+
+    // the two checksums from the puzzle, unless asked for a particular one
+    let names = match flags.get("--strategy") {
+        None => vec!["difference", "quotient"],
+        Some(name) if STRATEGIES.contains(&name) => vec![name],
+        Some(_) => {
+            let message = format!("--strategy must be one of: {}", STRATEGIES.join(", "));
+            return Err(Failure::Usage(message));
+        }
+    };
+
+    if let Some(threads) = flags.number("--threads", 1, 1024)? {
+        if output != "total" {
+            return Err(Failure::Usage(String::from("--threads only works with --output total")));
+        }
+        return print_parallel(flags.get("--input"), separator, &names, threads as usize);
+    }
+
+    let input = read_input(2, flags.get("--input")).map_err(Failure::Solver)?;
+    let separator = separator.unwrap_or_else(|| Separator::detect(&input));
+    let matrix = day02::parse_spreadsheet(&input, separator)?;
+
+    if output == "total" {
+        for name in names {
+            let strategy = day02::strategy(name).unwrap();
+            println!("{}: {}", name, day02::matrix_checksum(&matrix, &*strategy)?);
+        }
+        return Ok(());
+    }
+
+    // a row-by-row breakdown of each checksum (a JSON array of them, one object per checksum)
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    if output == "json" {
+        written(write!(out, "["))?;
+    }
+    for (i, &name) in names.iter().enumerate() {
+        let strategy = day02::strategy(name).unwrap();
+        let rows = diagnostics::diagnose(&matrix, &*strategy);
+        written(match output {
+            "json" => {
+                let separator = if i > 0 { ",\n" } else { "" };
+                write!(out, "{}", separator)
+                    .and_then(|()| diagnostics::write_json(name, &rows, &mut out))
+            }
+            _ => {
+                let separator = if i > 0 { "\n" } else { "" };
+                writeln!(out, "{}{}:", separator, name)
+                    .and_then(|()| diagnostics::write_table(&rows, &mut out))
+            }
+        })?;
+    }
+    if output == "json" {
+        written(writeln!(out, "]"))?;
+    }
+
+    // still fail if any of the checksums couldn't be worked out, now that it's clear why
+    for name in names {
+        day02::matrix_checksum(&matrix, &*day02::strategy(name).unwrap())?;
+    }
+    Ok(())
+}
+
+
+// the checksums worked out by streaming the input through a pool of threads, rather than reading
+// it all in first (once per checksum, so stdin can only have one)
+fn print_parallel(
+    path: Option<&str>,
+    separator: Option<Separator>,
+    names: &[&str],
+    threads: usize,
+) -> Result<(), Failure> {
+    let path = path.unwrap_or("data/day02.txt");
+    if path == "-" && names.len() > 1 {
+        let message = "--threads can only work out one --strategy when reading stdin";
+        return Err(Failure::Usage(String::from(message)));
+    }
+
+    for &name in names.iter() {
+        let strategy = day02::strategy(name).unwrap();
+        let total = match path {
+            "-" => parallel_checksum(io::stdin().lock(), separator, &*strategy, threads)?,
+            path => {
+                let file = File::open(path)
+                    .map_err(|e| Failure::Solver(format!("could not read {}: {}", path, e)))?;
+                parallel_checksum(BufReader::new(file), separator, &*strategy, threads)?
+            }
+        };
+        println!("{}: {}", name, total);
+    }
+    Ok(())
+}
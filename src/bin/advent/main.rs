@@ -0,0 +1,284 @@
+// Runs any day's solver against a puzzle input file.
+//
+//     advent run --day 6 --part 2 --input data/day06.txt
+//     advent run --day 6            (both parts of day 6)
+//     advent run                    (every day, printed as a summary table)
+//
+// Without --input, day N reads its puzzle input from data/dayNN.txt. An input of "-" reads stdin.
+//
+// There are also commands for digging into particular days' puzzles; see USAGE.
+
+extern crate advent2017;
+
+use std::collections::HashMap;
+use std::env;
+use std::fs::File;
+use std::io::{self, Read};
+use std::process;
+use std::time::Instant;
+
+mod captcha;
+mod checksum;
+mod spiral;
+mod tower;
+
+
+const USAGE: &str = "\
+usage: advent run [--day N] [--part 1|2] [--input PATH]
+       advent captcha [--offset N|half] [--radix N] [--input PATH]
+       advent checksum [--strategy NAME] [--output total|table|json] [--threads N]
+                       [--separator auto|tab|comma|whitespace] [--input PATH]
+       advent spiral (--index N | --point X,Y) [--winding counter-clockwise|clockwise]
+                     [--start right|up|left|down] [--origin N]
+       advent spiral --rings N [--label index|value] [--format text|svg]
+                     [--index N | --point X,Y] [--winding ...] [--start ...] [--origin N]
+       advent tower report [--format tree|csv] [--input PATH]
+       advent tower dot [--input PATH]
+       advent tower validate [--input PATH]
+       advent tower shell [--input PATH]";
+
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("run") => Flags::parse(&args[1..], &["--day", "--part", "--input"])
+            .and_then(|flags| run(&flags)),
+        Some("captcha") => Flags::parse(&args[1..], &["--offset", "--radix", "--input"])
+            .and_then(|flags| captcha::main(&flags)),
+        Some("checksum") => {
+            let allowed = ["--strategy", "--output", "--threads", "--separator", "--input"];
+            Flags::parse(&args[1..], &allowed).and_then(|flags| checksum::main(&flags))
+        }
+        Some("spiral") => {
+            let allowed = [
+                "--index", "--point", "--winding", "--start", "--origin", "--rings", "--label",
+                "--format",
+            ];
+            Flags::parse(&args[1..], &allowed).and_then(|flags| spiral::main(&flags))
+        }
+        Some("tower") => tower::main(&args[1..]),
+        Some("help") | Some("--help") | Some("-h") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some(command) => Err(Failure::Usage(format!("unknown command \"{}\"", command))),
+        None => Err(Failure::Usage(String::from("no command given"))),
+    };
+
+    match result {
+        Ok(()) => (),
+        Err(Failure::Usage(message)) => {
+            eprintln!("advent: {}\n{}", message, USAGE);
+            process::exit(2);
+        }
+        Err(Failure::Solver(message)) => {
+            eprintln!("advent: {}", message);
+            process::exit(1);
+        }
+    }
+}
+
+
+// why the runner gave up: bad command line arguments (exit status 2, along with the usage) or
+// a puzzle that couldn't be solved (exit status 1)
+pub enum Failure {
+    Usage(String),
+    Solver(String),
+}
+
+impl From<advent2017::Error> for Failure {
+    fn from(e: advent2017::Error) -> Failure {
+        Failure::Solver(e.to_string())
+    }
+}
+
+
+// the "--name value" options given to a command; each can also be given by its first letter,
+// e.g. "-d 6" for "--day 6", unless another of the command's options starts with the same letter
+pub struct Flags {
+    values: HashMap<&'static str, String>,
+}
+
+impl Flags {
+    pub fn parse(args: &[String], allowed: &[&'static str]) -> Result<Flags, Failure> {
+        let mut values = HashMap::new();
+        let mut args = args.iter();
+
+        while let Some(flag) = args.next() {
+            let names: Vec<&'static str> = allowed
+                .iter()
+                .filter(|name| *name == flag || (flag.len() == 2 && name[1..3] == flag[..]))
+                .cloned()
+                .collect();
+            let name = match *names.as_slice() {
+                [name] => name,
+                [] => return Err(Failure::Usage(format!("unknown option \"{}\"", flag))),
+                _ => {
+                    let message = format!("{} could mean any of {}", flag, names.join(", "));
+                    return Err(Failure::Usage(message));
+                }
+            };
+            let value = args.next()
+                .ok_or_else(|| Failure::Usage(format!("missing value for {}", flag)))?;
+            values.insert(name, value.clone());
+        }
+
+        Ok(Flags { values })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    // the value of a flag that must be a number in the given range
+    pub fn number(&self, name: &str, min: u32, max: u32) -> Result<Option<u32>, Failure> {
+        match self.get(name) {
+            None => Ok(None),
+            Some(value) => match value.parse() {
+                Ok(n) if min <= n && n <= max => Ok(Some(n)),
+                _ => Err(Failure::Usage(format!("{} must be a number from {} to {}", name, min, max))),
+            },
+        }
+    }
+
+    // the value of a flag that must be one of a few choices (the first is the default)
+    pub fn choice(&self, name: &str, choices: &[&'static str]) -> Result<&'static str, Failure> {
+        match self.get(name) {
+            None => Ok(choices[0]),
+            Some(value) => choices.iter().find(|&&choice| choice == value).cloned().ok_or_else(|| {
+                Failure::Usage(format!("{} must be one of: {}", name, choices.join(", ")))
+            }),
+        }
+    }
+}
+
+
+fn run(flags: &Flags) -> Result<(), Failure> {
+    let day = flags.number("--day", 1, advent2017::DAYS)?;
+    let part = flags.number("--part", 1, 2)?;
+    let path = flags.get("--input");
+
+    if path.is_some() && day.is_none() {
+        return Err(Failure::Usage(String::from("--input requires --day")));
+    }
+
+    let days: Vec<u32> = match day {
+        Some(day) => vec![day],
+        None => (1..=advent2017::DAYS).collect(),
+    };
+    let parts: Vec<u32> = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    // a single day and part just prints the bare answer, so it can be piped elsewhere
+    if let (&[day], &[part]) = (days.as_slice(), parts.as_slice()) {
+        let input = read_input(day, path).map_err(Failure::Solver)?;
+        return match advent2017::solve(day, part, &input) {
+            Some(Ok(answer)) => {
+                println!("{}", answer);
+                Ok(())
+            }
+            Some(Err(e)) => Err(Failure::Solver(format!("day {} part {}: {}", day, part, e))),
+            None => Err(Failure::Usage(format!("day {} part {} has no solver", day, part))),
+        };
+    }
+
+    // otherwise run everything requested and summarize the results in a table
+    let mut rows = vec![[
+        String::from("Day"),
+        String::from("Part"),
+        String::from("Answer"),
+        String::from("Time"),
+    ]];
+    let mut failures = 0;
+    for &day in days.iter() {
+        let input = read_input(day, path);
+        for &part in parts.iter() {
+            let start = Instant::now();
+            let answer = match input {
+                Ok(ref input) => match advent2017::solve(day, part, input) {
+                    Some(Ok(answer)) => answer,
+                    Some(Err(e)) => {
+                        failures += 1;
+                        format!("(error: {})", e)
+                    }
+                    None => String::from("(not solved)"),
+                },
+                Err(ref message) => {
+                    failures += 1;
+                    format!("({})", message)
+                }
+            };
+            let elapsed = start.elapsed();
+            rows.push([
+                day.to_string(),
+                part.to_string(),
+                answer,
+                format!("{:.1} ms", elapsed.as_secs_f64() * 1000.0),
+            ]);
+        }
+    }
+    print_table(&rows);
+
+    match failures {
+        0 => Ok(()),
+        1 => Err(Failure::Solver(String::from("1 puzzle failed"))),
+        n => Err(Failure::Solver(format!("{} puzzles failed", n))),
+    }
+}
+
+
+// the result of writing a command's output; a closed pipe (e.g. output piped into `head`) just
+// means nobody wants the rest of it
+pub fn written(result: io::Result<()>) -> Result<(), Failure> {
+    match result {
+        Err(ref e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        Err(e) => Err(Failure::Solver(format!("could not write output: {}", e))),
+        Ok(()) => Ok(()),
+    }
+}
+
+
+// read the puzzle input for a day from the given path, or data/dayNN.txt if there isn't one
+pub fn read_input(day: u32, path: Option<&str>) -> Result<String, String> {
+    let path = match path {
+        Some(path) => path.to_string(),
+        None => format!("data/day{:02}.txt", day),
+    };
+
+    if path == "-" {
+        return advent2017::read_stdin().map_err(|e| e.to_string());
+    }
+
+    let mut input = String::new();
+    File::open(&path)
+        .and_then(|mut file| file.read_to_string(&mut input))
+        .map_err(|e| format!("could not read {}: {}", path, e))?;
+    Ok(input)
+}
+
+
+// print rows as left-aligned columns, with the first row treated as a header
+fn print_table(rows: &[[String; 4]]) {
+    let mut widths = [0; 4];
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    for (i, row) in rows.iter().enumerate() {
+        let line: Vec<String> = row.iter()
+            .zip(widths.iter())
+            .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        println!("{}", line.join("  ").trim_end());
+
+        if i == 0 {
+            let rule: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+            println!("{}", rule.join("  "));
+        }
+    }
+}
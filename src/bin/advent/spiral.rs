@@ -0,0 +1,124 @@
+// `advent spiral`: where a day 3 square number sits on the spiral, or which square number sits at
+// a given point, on the puzzle's spiral or one winding the other way, starting in another
+// direction or numbered from somewhere else. With --rings, it draws the spiral instead.
+
+use advent2017::day03::render::{self, Label};
+use advent2017::day03::{Direction, Point, Spiral, Winding};
+use std::io::{self, Write};
+
+use {written, Failure, Flags};
+
+
+pub fn main(flags: &Flags) -> Result<(), Failure> {
+    let spiral = spiral(flags)?;
+    let target = target(flags, &spiral)?;
+
+    if let Some(rings) = flags.number("--rings", 0, 1000)? {
+        return render(flags, &spiral, rings, target.map(|(_, point)| point));
+    }
+
+    let center = Point { x: 0, y: 0 };
+    match (target, flags.get("--index")) {
+        (Some((index, point)), Some(_)) => {
+            let steps = point.manhattan(&center);
+            println!("square {} is at {}, {} steps from the center", index, point, steps);
+        }
+        (Some((index, point)), None) => {
+            let steps = point.manhattan(&center);
+            println!("{} is square {}, {} steps from the center", point, index, steps);
+        }
+        (None, _) => return Err(Failure::Usage(String::from("give one of --index or --point"))),
+    }
+    Ok(())
+}
+
+
+// draw the first rings of the spiral, with the target square highlighted in an SVG
+fn render(
+    flags: &Flags,
+    spiral: &Spiral,
+    rings: u32,
+    target: Option<Point>,
+) -> Result<(), Failure> {
+    let label = match flags.choice("--label", &["index", "value"])? {
+        "value" => Label::Value,
+        _ => Label::Index,
+    };
+    let format = flags.choice("--format", &["text", "svg"])?;
+
+    let squares = render::squares(spiral, rings, label);
+    if let Some(target) = target {
+        if !squares.iter().any(|square| square.point == target) {
+            let message = format!("the square at {} is further out than ring {}", target, rings);
+            return Err(Failure::Usage(message));
+        }
+    }
+
+    let output = match format {
+        "svg" => render::to_svg(&squares, target),
+        _ => render::to_text(&squares),
+    };
+    written(io::stdout().write_all(output.as_bytes()))
+}
+
+
+// the number and location of the square given by --index or --point, if either is
+fn target(flags: &Flags, spiral: &Spiral) -> Result<Option<(u64, Point)>, Failure> {
+    match (flags.get("--index"), flags.get("--point")) {
+        (Some(index), None) => match index.parse() {
+            Ok(index) if index >= spiral.origin => Ok(Some((index, spiral.point(index).unwrap()))),
+            _ => {
+                let range = format!("from {} to {}", spiral.origin, u64::MAX);
+                Err(Failure::Usage(format!("--index must be a number {}", range)))
+            }
+        },
+        (None, Some(point)) => {
+            let point = parse_point(point).ok_or_else(|| {
+                Failure::Usage(String::from("--point must be a pair of numbers like \"3,-2\""))
+            })?;
+            let index = spiral.index(&point).ok_or_else(|| {
+                let message = format!("the square at {} is numbered past {}", point, u64::MAX);
+                Failure::Solver(message)
+            })?;
+            Ok(Some((index, point)))
+        }
+        (None, None) => Ok(None),
+        (Some(_), Some(_)) => {
+            Err(Failure::Usage(String::from("give only one of --index or --point")))
+        }
+    }
+}
+
+
+// the spiral described by the --winding, --start and --origin flags (the puzzle's by default)
+pub fn spiral(flags: &Flags) -> Result<Spiral, Failure> {
+    let winding = match flags.choice("--winding", &["counter-clockwise", "clockwise"])? {
+        "clockwise" => Winding::Clockwise,
+        _ => Winding::CounterClockwise,
+    };
+    let start = match flags.choice("--start", &["right", "up", "left", "down"])? {
+        "up" => Direction::Up,
+        "left" => Direction::Left,
+        "down" => Direction::Down,
+        _ => Direction::Right,
+    };
+    let origin = match flags.get("--origin") {
+        None => 1,
+        Some(origin) => origin.parse().map_err(|_| {
+            Failure::Usage(format!("--origin must be a number from 0 to {}", u64::MAX))
+        })?,
+    };
+    Ok(Spiral::new(winding, start, origin))
+}
+
+
+// "x,y", optionally in parentheses and with spaces around the numbers
+fn parse_point(s: &str) -> Option<Point> {
+    let s = s.trim();
+    let s = s.strip_prefix('(').and_then(|s| s.strip_suffix(')')).unwrap_or(s);
+    let mut numbers = s.split(',').map(|n| n.trim().parse());
+    match (numbers.next(), numbers.next(), numbers.next()) {
+        (Some(Ok(x)), Some(Ok(y)), None) => Some(Point { x, y }),
+        _ => None,
+    }
+}
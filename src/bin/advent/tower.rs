@@ -0,0 +1,171 @@
+// `advent tower ...`: commands for inspecting a day 7 program tower (data/day07.txt by default).
+
+use advent2017::Solution;
+use advent2017::day07::{self, Day07, Program, Tower};
+use advent2017::day07::{dot, query, report, validate};
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use {read_input, written, Failure, Flags};
+
+
+pub fn main(args: &[String]) -> Result<(), Failure> {
+    match args.first().map(String::as_str) {
+        Some("report") => Flags::parse(&args[1..], &["--format", "--input"])
+            .and_then(|flags| print_report(&flags)),
+        Some("dot") => Flags::parse(&args[1..], &["--input"]).and_then(|flags| print_dot(&flags)),
+        Some("validate") => Flags::parse(&args[1..], &["--input"])
+            .and_then(|flags| print_problems(&flags)),
+        Some("shell") => Flags::parse(&args[1..], &["--input"]).and_then(|flags| shell(&flags)),
+        Some(command) => Err(Failure::Usage(format!("unknown tower command \"{}\"", command))),
+        None => Err(Failure::Usage(String::from("no tower command given"))),
+    }
+}
+
+
+fn load(flags: &Flags) -> Result<Vec<Program>, Failure> {
+    let input = read_input(7, flags.get("--input")).map_err(Failure::Solver)?;
+    Ok(Day07::parse(&input)?)
+}
+
+
+// per-program weights and balance, as an indented tree or CSV
+fn print_report(flags: &Flags) -> Result<(), Failure> {
+    let format = flags.choice("--format", &["tree", "csv"])?;
+
+    let programs = load(flags)?;
+    let tower = Tower::validated(&programs)?;
+    let root = day07::get_root(&tower.tree)?;
+    let rows = report::report(&tower, root)?;
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    written(match format {
+        "csv" => report::write_csv(&rows, &mut out),
+        _ => report::write_tree(&rows, &mut out),
+    })
+}
+
+
+// the tower as a Graphviz graph, to be rendered with `dot`
+fn print_dot(flags: &Flags) -> Result<(), Failure> {
+    let programs = load(flags)?;
+    let tower = Tower::validated(&programs)?;
+    let root = day07::get_root(&tower.tree)?;
+    let dot = dot::to_dot(&tower, root)?;
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    written(out.write_all(dot.as_bytes()))
+}
+
+
+// every problem that keeps the programs from forming a single tower, or a summary of the tower
+// if there aren't any
+fn print_problems(flags: &Flags) -> Result<(), Failure> {
+    let programs = load(flags)?;
+    let problems = validate::validate(&programs);
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    if problems.is_empty() {
+        let tower = Tower::new(&programs);
+        let root = day07::get_root(&tower.tree)?;
+        return written(writeln!(out, "ok: {} programs, rooted at {}", programs.len(), root));
+    }
+
+    for problem in problems.iter() {
+        written(writeln!(out, "{}", problem))?;
+    }
+    match problems.len() {
+        1 => Err(Failure::Solver(String::from("found 1 problem"))),
+        n => Err(Failure::Solver(format!("found {} problems", n))),
+    }
+}
+
+
+const SHELL_HELP: &str = "\
+commands: weight NAME      its own weight and the total weight it's holding up
+          parent NAME      the program holding it up
+          path NAME        every program from the root up to it
+          siblings NAME    the other programs its parent is holding up
+          heaviest [NAME]  the heaviest program it's holding up (the root's, without a name)
+          leaves [NAME]    the programs at the top of the tower above it
+          root, help, quit";
+
+
+// answer questions about the tower, one per line of stdin, until quit or end of input
+fn shell(flags: &Flags) -> Result<(), Failure> {
+    if flags.get("--input") == Some("-") {
+        let message = "the shell reads its commands from stdin, so --input can't be \"-\"";
+        return Err(Failure::Usage(String::from(message)));
+    }
+
+    let programs = load(flags)?;
+    let explorer = query::Explorer::new(&programs)?;
+
+    let stdin = io::stdin();
+    let interactive = stdin.is_terminal();
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+
+    if interactive {
+        let root = explorer.root();
+        written(writeln!(out, "{} programs, rooted at {}; try \"help\"", programs.len(), root))?;
+    }
+    let mut lines = stdin.lock().lines();
+    loop {
+        if interactive {
+            written(write!(out, "> ").and_then(|()| out.flush()))?;
+        }
+        let line = match lines.next() {
+            Some(line) => {
+                line.map_err(|e| Failure::Solver(format!("could not read command: {}", e)))?
+            }
+            None => return Ok(()),
+        };
+
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let answer = match words.as_slice() {
+            [] => continue,
+            ["quit"] | ["exit"] => return Ok(()),
+            words => answer(&explorer, words),
+        };
+        written(match answer {
+            Ok(answer) => writeln!(out, "{}", answer),
+            Err(message) => writeln!(out, "error: {}", message),
+        })?;
+    }
+}
+
+
+// the answer to one shell command, or what was wrong with it
+fn answer(explorer: &query::Explorer, words: &[&str]) -> Result<String, String> {
+    let list = |programs: Vec<&str>| match programs.len() {
+        0 => String::from("(none)"),
+        _ => programs.join(" "),
+    };
+
+    let answer = match *words {
+        ["help"] => Ok(String::from(SHELL_HELP)),
+        ["root"] => Ok(explorer.root().to_string()),
+        ["weight", name] => explorer
+            .weights(name)
+            .map(|(weight, total)| format!("{} (total {})", weight, total)),
+        ["parent", name] => explorer
+            .parent(name)
+            .map(|parent| parent.unwrap_or("(none, it's the root)").to_string()),
+        ["path", name] => explorer.path(name).map(|path| path.join(" -> ")),
+        ["siblings", name] => explorer.siblings(name).map(list),
+        ["heaviest"] | ["heaviest", _] => explorer
+            .heaviest(words.get(1).cloned().unwrap_or_else(|| explorer.root()))
+            .map(|heaviest| match heaviest {
+                Some((child, total)) => format!("{} (total {})", child, total),
+                None => String::from("(none, it's holding nothing up)"),
+            }),
+        ["leaves"] | ["leaves", _] => explorer
+            .leaves(words.get(1).cloned().unwrap_or_else(|| explorer.root()))
+            .map(list),
+        _ => return Err(format!("don't know how to \"{}\"; try \"help\"", words.join(" "))),
+    };
+    answer.map_err(|e| e.to_string())
+}
@@ -1,32 +1,14 @@
 // http://adventofcode.com/2017/day/1
 
-extern crate unicode_segmentation;
+extern crate advent2017;
 
-use std::io;
-use unicode_segmentation::UnicodeSegmentation;
+use advent2017::Solution;
+use advent2017::day01::Day01;
 
-fn main() {
-    // read puzzle input as single line from stdin
-    let mut puzzle_input = String::new();
-    io::stdin().read_line(&mut puzzle_input).unwrap();
-
-    // parse puzzle input string to vector of u32
-    let digits: Vec<u32> = puzzle_input
-        .trim()
-        .graphemes(true)
-        .map(|g| g.parse().unwrap())
-        .collect();
-
-    // initialize "previous" digit to the last digit of the vector (because it's a ring)
-    let mut prev_d = digits.iter().rev().next().unwrap();
 
-    let mut sum = 0;
-    for d in digits.iter() {
-        if d == prev_d {
-            sum += d;
-        }
-        prev_d = d;
-    }
-
-    println!("{}", sum);
+fn main() {
+    let answer = advent2017::read_stdin()
+        .and_then(|input| Day01::parse(&input))
+        .and_then(|input| Day01::part_one(&input));
+    advent2017::print_answer(answer);
 }
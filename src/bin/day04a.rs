@@ -1,47 +1,14 @@
 // http://adventofcode.com/2017/day/4
 
-extern crate unicode_segmentation;
+extern crate advent2017;
 
-use std::collections::HashSet;
-use std::io::{self, BufRead};
-use unicode_segmentation::UnicodeSegmentation;
+use advent2017::Solution;
+use advent2017::day04::Day04;
 
 
 fn main() {
-    let stdin = io::stdin();
-    let valid_count: i32 = stdin
-        .lock()
-        .lines()
-        .map(|line| if is_valid(&line.unwrap()) { 1 } else { 0 })
-        .sum();
-
-    println!("{}", valid_count);
-}
-
-
-fn is_valid(line: &str) -> bool {
-    let mut used_words = HashSet::new();
-    let collisions: HashSet<bool> = line.unicode_words().map(|w| used_words.insert(w)).collect();
-    !collisions.contains(&false)
-}
-
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_1() {
-        assert_eq!(is_valid("aa bb cc dd ee"), true);
-    }
-
-    #[test]
-    fn test_2() {
-        assert_eq!(is_valid("aa bb cc dd aa"), false);
-    }
-
-    #[test]
-    fn test_3() {
-        assert_eq!(is_valid("aa bb cc dd aaa"), true);
-    }
+    let answer = advent2017::read_stdin()
+        .and_then(|input| Day04::parse(&input))
+        .and_then(|input| Day04::part_one(&input));
+    advent2017::print_answer(answer);
 }
@@ -1,68 +1,14 @@
 // http://adventofcode.com/2017/day/4
 
-extern crate unicode_segmentation;
+extern crate advent2017;
 
-use std::collections::{BTreeSet, BTreeMap};
-use std::io::{self, BufRead};
-use unicode_segmentation::UnicodeSegmentation;
+use advent2017::Solution;
+use advent2017::day04::Day04;
 
 
 fn main() {
-    let stdin = io::stdin();
-    let valid_count: i32 = stdin
-        .lock()
-        .lines()
-        .map(|line| if is_valid(&line.unwrap()) { 1 } else { 0 })
-        .sum();
-
-    println!("{}", valid_count);
-}
-
-
-fn is_valid(line: &str) -> bool {
-    let mut used_indexes = BTreeSet::new();
-    let collisions: BTreeSet<bool> = line.unicode_words()
-        .map(|w| used_indexes.insert(create_index_from(w)))
-        .collect();
-    !collisions.contains(&false)
-}
-
-
-fn create_index_from(word: &str) -> BTreeMap<&str, i32> {
-    let mut index = BTreeMap::new();
-    for g in word.graphemes(true) {
-        *(index.entry(g).or_insert(0)) += 1;
-    }
-    index
-}
-
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_1() {
-        assert_eq!(is_valid("abcde fghij"), true);
-    }
-
-    #[test]
-    fn test_2() {
-        assert_eq!(is_valid("abcde xyz ecdab"), false);
-    }
-
-    #[test]
-    fn test_3() {
-        assert_eq!(is_valid("a ab abc abd abf abj"), true);
-    }
-
-    #[test]
-    fn test_4() {
-        assert_eq!(is_valid("iiii oiii ooii oooi oooo"), true);
-    }
-
-    #[test]
-    fn test_5() {
-        assert_eq!(is_valid("oiii ioii iioi iiio"), false);
-    }
+    let answer = advent2017::read_stdin()
+        .and_then(|input| Day04::parse(&input))
+        .and_then(|input| Day04::part_two(&input));
+    advent2017::print_answer(answer);
 }
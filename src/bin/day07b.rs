@@ -0,0 +1,14 @@
+// http://adventofcode.com/2017/day/7
+
+extern crate advent2017;
+
+use advent2017::Solution;
+use advent2017::day07::Day07;
+
+
+fn main() {
+    let answer = advent2017::read_stdin()
+        .and_then(|input| Day07::parse(&input))
+        .and_then(|input| Day07::part_two(&input));
+    advent2017::print_answer(answer);
+}
@@ -0,0 +1,516 @@
+// http://adventofcode.com/2017/day/1
+
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::str;
+use unicode_segmentation::UnicodeSegmentation;
+
+use {Error, Result, Solution};
+use error::abbreviate;
+
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<u32>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    // parse puzzle input string to vector of digits
+    fn parse(input: &str) -> Result<Vec<u32>> {
+        parse_symbols(input, &Alphabet::decimal())
+    }
+
+    fn part_one(digits: &Vec<u32>) -> Result<u64> {
+        Ok(sum_matching_adjacent(digits))
+    }
+
+    fn part_two(digits: &Vec<u32>) -> Result<u64> {
+        Ok(sum_matching_opposite(digits))
+    }
+}
+
+
+// the symbols a captcha can be written in, and what each one is worth
+pub struct Alphabet {
+    symbols: Symbols,
+}
+
+enum Symbols {
+    Radix(u32),
+    Map(HashMap<String, u32>),
+}
+
+impl Alphabet {
+    pub fn decimal() -> Alphabet {
+        Alphabet { symbols: Symbols::Radix(10) }
+    }
+
+    // digits in the given base, from 2 to 36 (after 9 come the letters a to z, in either case)
+    pub fn radix(radix: u32) -> Option<Alphabet> {
+        match radix {
+            2..=36 => Some(Alphabet { symbols: Symbols::Radix(radix) }),
+            _ => None,
+        }
+    }
+
+    // any symbols (each a single grapheme) with any values
+    pub fn map(symbols: HashMap<String, u32>) -> Alphabet {
+        Alphabet { symbols: Symbols::Map(symbols) }
+    }
+
+    pub fn value(&self, symbol: &str) -> Option<u32> {
+        match self.symbols {
+            Symbols::Radix(radix) => {
+                let mut chars = symbol.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => c.to_digit(radix),
+                    _ => None,
+                }
+            }
+            Symbols::Map(ref symbols) => symbols.get(symbol).cloned(),
+        }
+    }
+
+    // the value of a symbol that's a single character (without allocating a string for it)
+    fn char_value(&self, c: char) -> Option<u32> {
+        match self.symbols {
+            Symbols::Radix(radix) => c.to_digit(radix),
+            Symbols::Map(ref symbols) => symbols.get(c.encode_utf8(&mut [0; 4]) as &str).cloned(),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self.symbols {
+            Symbols::Radix(10) => String::from("a digit"),
+            Symbols::Radix(radix) => format!("a base {} digit", radix),
+            Symbols::Map(_) => String::from("a symbol in the alphabet"),
+        }
+    }
+}
+
+
+// the value of each symbol of the input (ignoring line breaks and whitespace at the ends of lines)
+pub fn parse_symbols(input: &str, alphabet: &Alphabet) -> Result<Vec<u32>> {
+    let mut values = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        for (j, g) in line.trim_end().graphemes(true).enumerate() {
+            match alphabet.value(g) {
+                Some(value) => values.push(value),
+                None => {
+                    let message =
+                        format!("expected {}, found \"{}\"", alphabet.describe(), abbreviate(g));
+                    return Err(Error::parse(i + 1, j + 1, message));
+                }
+            }
+        }
+    }
+    Ok(values)
+}
+
+
+// how far ahead around the ring each digit is compared with, given the length of the ring
+pub enum Offset {
+    Fixed(usize),
+    HalfLength,
+    Custom(Box<dyn Fn(usize) -> usize>),
+}
+
+impl Offset {
+    pub fn resolve(&self, length: usize) -> usize {
+        match *self {
+            Offset::Fixed(k) => k,
+            Offset::HalfLength => length / 2,
+            Offset::Custom(ref offset) => offset(length),
+        }
+    }
+}
+
+
+// sum every digit that matches the digit the given offset ahead of it (the list is a ring, so an
+// offset past the end wraps back around to the start); with each digit at most u32::MAX, the u64
+// sum could only overflow on a slice of more than 2^32 digits
+pub fn captcha(digits: &[u32], offset: &Offset) -> u64 {
+    let length = digits.len();
+    if length == 0 {
+        return 0;
+    }
+    let offset = offset.resolve(length) % length;
+
+    digits.iter()
+        .zip(digits.iter().cycle().skip(offset))
+        .filter(|&(a, b)| a == b)
+        .map(|(&a, _)| u64::from(a))
+        .sum()
+}
+
+
+// sum every digit that matches the digit after it
+pub fn sum_matching_adjacent(digits: &[u32]) -> u64 {
+    captcha(digits, &Offset::Fixed(1))
+}
+
+
+// sum every digit that matches the digit halfway around the ring from it
+pub fn sum_matching_opposite(digits: &[u32]) -> u64 {
+    captcha(digits, &Offset::HalfLength)
+}
+
+
+// Streaming versions of captcha, for inputs too big to read into memory all at once. Whitespace
+// anywhere in the input is skipped; anything else that isn't in the alphabet is an error. The input
+// is read a character at a time rather than a grapheme at a time, so an alphabet symbol made of
+// several characters (like an e followed by a combining accent) is never recognized.
+
+// sum every digit that matches the digit a fixed offset ahead of it, reading the input just once
+// and keeping only the first and last `offset` digits in memory
+pub fn stream_captcha<R>(mut input: R, offset: usize, alphabet: &Alphabet) -> Result<u64>
+where
+    R: BufRead,
+{
+    let mut scanner = DigitScanner::new(alphabet);
+    let mut chunk = Vec::new();
+    let mut length = 0;
+    let mut first = Vec::new();
+    let mut window = VecDeque::new(); // the last `offset` digits read so far
+    let mut sum = 0;
+
+    while scanner.read_chunk(&mut input, &mut chunk)? > 0 {
+        length += chunk.len();
+        for &digit in chunk.iter() {
+            if first.len() < offset {
+                first.push(digit);
+            }
+            if offset == 0 {
+                sum = add(sum, digit)?;
+                continue;
+            }
+            if window.len() == offset && window.pop_front() == Some(digit) {
+                sum = add(sum, digit)?;
+            }
+            window.push_back(digit);
+        }
+    }
+
+    // a ring no longer than the offset is all in memory anyway, and wraps around itself
+    if length <= offset {
+        return Ok(captcha(&first, &Offset::Fixed(offset)));
+    }
+
+    // the pairs that wrap around the end: the last `offset` digits and the first
+    for (&a, &b) in window.iter().zip(first.iter()) {
+        if a == b {
+            sum = add(sum, a)?;
+        }
+    }
+    Ok(sum)
+}
+
+
+// sum every digit that matches the digit the given offset ahead of it, using only a couple of
+// chunks of memory however long the input is: once to count the digits (which offsets like
+// HalfLength depend on), and then again from two places at once, seeking back and forth
+pub fn seek_captcha<R>(input: R, offset: &Offset, alphabet: &Alphabet) -> Result<u64>
+where
+    R: Read + Seek,
+{
+    let mut input = BufReader::with_capacity(CHUNK_SIZE, input);
+    let start = input.stream_position()?;
+
+    let mut scanner = DigitScanner::new(alphabet);
+    let mut chunk = Vec::new();
+    let mut length = 0;
+    while scanner.read_chunk(&mut input, &mut chunk)? > 0 {
+        length += chunk.len();
+    }
+    if length == 0 {
+        return Ok(0);
+    }
+
+    let mut ahead = RingCursor::new(start, alphabet);
+    for _ in 0..offset.resolve(length) % length {
+        ahead.next(&mut input)?;
+    }
+
+    let mut behind = RingCursor::new(start, alphabet);
+    let mut sum = 0;
+    for _ in 0..length {
+        let digit = behind.next(&mut input)?;
+        if ahead.next(&mut input)? == digit {
+            sum = add(sum, digit)?;
+        }
+    }
+    Ok(sum)
+}
+
+
+// a streamed input has no limit on its length, so its sum is checked for overflow
+fn add(sum: u64, digit: u32) -> Result<u64> {
+    sum.checked_add(u64::from(digit))
+        .ok_or_else(|| Error::unsolvable("the captcha doesn't fit in 64 bits"))
+}
+
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+
+// turns bytes of input into the values of the alphabet's symbols, keeping track of where it is in
+// the input for error messages
+struct DigitScanner<'a> {
+    alphabet: &'a Alphabet,
+    line: usize,
+    column: usize,
+    partial: Vec<u8>, // the start of a UTF-8 character split across chunks
+}
+
+impl<'a> DigitScanner<'a> {
+    fn new(alphabet: &'a Alphabet) -> DigitScanner<'a> {
+        DigitScanner {
+            alphabet,
+            line: 1,
+            column: 0,
+            partial: Vec::new(),
+        }
+    }
+
+    // replace the contents of chunk with the digits in the next chunk of input, returning how many
+    // bytes were read (0 at the end of the input)
+    fn read_chunk<R: BufRead>(&mut self, input: &mut R, chunk: &mut Vec<u32>) -> Result<usize> {
+        chunk.clear();
+        let bytes = {
+            let bytes = input.fill_buf()?;
+            for &byte in bytes.iter() {
+                if let Some(digit) = self.scan(byte)? {
+                    chunk.push(digit);
+                }
+            }
+            bytes.len()
+        };
+        input.consume(bytes);
+        if bytes == 0 && !self.partial.is_empty() {
+            return Err(Error::parse(self.line, self.column + 1, "expected a UTF-8 character"));
+        }
+        Ok(bytes)
+    }
+
+    fn scan(&mut self, byte: u8) -> Result<Option<u32>> {
+        if byte == b'\n' {
+            self.line += 1;
+            self.column = 0;
+            return Ok(None);
+        }
+
+        // most inputs are all ASCII, which needs no decoding
+        let c = match (byte.is_ascii(), self.partial.is_empty()) {
+            (true, true) => byte as char,
+            _ => {
+                self.partial.push(byte);
+                match str::from_utf8(&self.partial) {
+                    Ok(s) => {
+                        let c = s.chars().next().unwrap();
+                        self.partial.clear();
+                        c
+                    }
+                    Err(ref e) if e.error_len().is_none() => return Ok(None),
+                    Err(_) => {
+                        let message = "expected a UTF-8 character";
+                        return Err(Error::parse(self.line, self.column + 1, message));
+                    }
+                }
+            }
+        };
+        self.column += 1;
+
+        match self.alphabet.char_value(c) {
+            Some(digit) => Ok(Some(digit)),
+            None if c.is_whitespace() => Ok(None),
+            None => {
+                let message = format!("expected {}, found \"{}\"", self.alphabet.describe(), c);
+                Err(Error::parse(self.line, self.column, message))
+            }
+        }
+    }
+}
+
+
+// reads digits from one place in a seekable input, going back to the start at the end of it, so
+// that several can take turns reading from the same input
+struct RingCursor<'a> {
+    start: u64,
+    position: u64,
+    scanner: DigitScanner<'a>, // kept between reads, in case a chunk ends partway into a character
+    digits: VecDeque<u32>,
+}
+
+impl<'a> RingCursor<'a> {
+    fn new(start: u64, alphabet: &'a Alphabet) -> RingCursor<'a> {
+        RingCursor {
+            start,
+            position: start,
+            scanner: DigitScanner::new(alphabet),
+            digits: VecDeque::new(),
+        }
+    }
+
+    // the next digit (the input must already have been checked to have at least one)
+    fn next<R: Read + Seek>(&mut self, input: &mut BufReader<R>) -> Result<u32> {
+        let mut chunk = Vec::new();
+        while self.digits.is_empty() {
+            input.seek(SeekFrom::Start(self.position))?;
+            match self.scanner.read_chunk(input, &mut chunk)? {
+                0 => self.position = self.start,
+                bytes => self.position += bytes as u64,
+            }
+            self.digits.extend(chunk.iter());
+        }
+        Ok(self.digits.pop_front().unwrap())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part_one(input: &str) -> u64 {
+        Day01::part_one(&Day01::parse(input).unwrap()).unwrap()
+    }
+
+    fn part_two(input: &str) -> u64 {
+        Day01::part_two(&Day01::parse(input).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn part_one_1() { assert_eq!(part_one("1122"), 3); }
+
+    #[test]
+    fn part_one_2() { assert_eq!(part_one("1111"), 4); }
+
+    #[test]
+    fn part_one_3() { assert_eq!(part_one("1234"), 0); }
+
+    #[test]
+    fn part_one_4() { assert_eq!(part_one("91212129"), 9); }
+
+    #[test]
+    fn part_two_1() { assert_eq!(part_two("1212"), 6); }
+
+    #[test]
+    fn part_two_2() { assert_eq!(part_two("1221"), 0); }
+
+    #[test]
+    fn part_two_3() { assert_eq!(part_two("123425"), 4); }
+
+    #[test]
+    fn part_two_4() { assert_eq!(part_two("123123"), 12); }
+
+    #[test]
+    fn part_two_5() { assert_eq!(part_two("12131415"), 4); }
+
+    #[test]
+    fn offsets() {
+        let digits = Day01::parse("12131415").unwrap();
+        assert_eq!(captcha(&digits, &Offset::Fixed(0)), 1 + 2 + 1 + 3 + 1 + 4 + 1 + 5);
+        assert_eq!(captcha(&digits, &Offset::Fixed(2)), 4);
+        assert_eq!(captcha(&digits, &Offset::Fixed(10)), 4);
+        assert_eq!(captcha(&digits, &Offset::Custom(Box::new(|length| length - 2))), 4);
+        assert_eq!(captcha(&[], &Offset::HalfLength), 0);
+    }
+
+    // 10,000 matching nines sum to 90,000, which didn't fit in the u16 part two used to sum into
+    #[test]
+    fn long_input() {
+        let nines = "9".repeat(10_000);
+        assert_eq!(part_one(&nines), 90_000);
+        assert_eq!(part_two(&nines), 90_000);
+    }
+
+    #[test]
+    fn streaming() {
+        use std::io::Cursor;
+
+        let decimal = Alphabet::decimal();
+        for input in ["", "7", "1122", "91212129", "1212\n12\r\n", "123425", "12131415"].iter() {
+            let digits = Day01::parse(input).unwrap();
+            for k in 0..10 {
+                let expected = captcha(&digits, &Offset::Fixed(k));
+                assert_eq!(stream_captcha(input.as_bytes(), k, &decimal).unwrap(), expected);
+                let sum = seek_captcha(Cursor::new(input), &Offset::Fixed(k), &decimal).unwrap();
+                assert_eq!(sum, expected);
+            }
+            let expected = sum_matching_opposite(&digits);
+            let sum = seek_captcha(Cursor::new(input), &Offset::HalfLength, &decimal).unwrap();
+            assert_eq!(sum, expected);
+        }
+
+        let error = stream_captcha("1234\n12a4".as_bytes(), 1, &decimal).unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 3: expected a digit, found \"a\"");
+    }
+
+    #[test]
+    fn radix() {
+        let digits = parse_symbols("ffA0fF", &Alphabet::radix(16).unwrap()).unwrap();
+        assert_eq!(digits, vec![15, 15, 10, 0, 15, 15]);
+        assert_eq!(sum_matching_adjacent(&digits), 15 + 15 + 15);
+        assert_eq!(sum_matching_opposite(&digits), 15 + 15);
+
+        assert!(Alphabet::radix(1).is_none());
+        assert!(Alphabet::radix(37).is_none());
+        let error = parse_symbols("0110\n012", &Alphabet::radix(2).unwrap()).unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 3: expected a base 2 digit, found \"2\"");
+    }
+
+    #[test]
+    fn alphabet() {
+        let symbols = vec![("♠", 1), ("♥", 20), ("♦", 300), ("e\u{301}", 4000)];
+        let symbols = symbols.into_iter().map(|(s, v)| (s.to_string(), v)).collect();
+        let alphabet = Alphabet::map(symbols);
+        let digits = parse_symbols("♠♠♥e\u{301}e\u{301}♦♠", &alphabet).unwrap();
+        assert_eq!(digits, vec![1, 1, 20, 4000, 4000, 300, 1]);
+        assert_eq!(sum_matching_adjacent(&digits), 1 + 4000 + 1);
+
+        let error = parse_symbols("♠♣", &alphabet).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 2: expected a symbol in the alphabet, found \"♣\""
+        );
+    }
+
+    #[test]
+    fn streaming_alphabet() {
+        use std::io::Cursor;
+
+        let hex = Alphabet::radix(16).unwrap();
+        assert_eq!(stream_captcha("ffA0fF".as_bytes(), 1, &hex).unwrap(), 15 + 15 + 15);
+        let sum = seek_captcha(Cursor::new("ffA0fF"), &Offset::HalfLength, &hex).unwrap();
+        assert_eq!(sum, 15 + 15);
+
+        // multi-byte characters, split across every chunk boundary
+        let symbols = vec![("♠", 1), ("♥", 20), ("x", u32::MAX)];
+        let symbols = symbols.into_iter().map(|(s, v)| (s.to_string(), v)).collect();
+        let alphabet = Alphabet::map(symbols);
+        let input = "♠♠♥\n♥♠ ♠".repeat(CHUNK_SIZE / 5);
+        let digits: Vec<u32> = input.chars().filter_map(|c| alphabet.char_value(c)).collect();
+        for k in [1, 7].iter() {
+            let expected = captcha(&digits, &Offset::Fixed(*k));
+            assert_eq!(stream_captcha(input.as_bytes(), *k, &alphabet).unwrap(), expected);
+            let sum = seek_captcha(Cursor::new(&input), &Offset::Fixed(*k), &alphabet).unwrap();
+            assert_eq!(sum, expected);
+        }
+
+        let error = stream_captcha("♠\n♠♣".as_bytes(), 1, &alphabet).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: expected a symbol in the alphabet, found \"♣\""
+        );
+        let error = stream_captcha(&b"\xe2\x99"[..], 1, &alphabet).unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 1: expected a UTF-8 character");
+        assert_eq!(stream_captcha("xx".as_bytes(), 1, &alphabet).unwrap(), 2 * u64::from(u32::MAX));
+    }
+
+    #[test]
+    fn bad_digit() {
+        let error = Day01::parse("1234\n12a4").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 3: expected a digit, found \"a\"");
+    }
+}
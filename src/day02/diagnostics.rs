@@ -0,0 +1,242 @@
+// Row-by-row breakdown of a spreadsheet checksum: which cells each row's checksum came from and
+// what it added to the total, for tracking down why two checksums disagree.
+
+use std::io::{self, Write};
+
+use super::RowChecksum;
+
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cell {
+    pub role: &'static str, // the part the cell played in the checksum, e.g. "min" or "divisor"
+    pub column: usize,      // one-indexed, like the columns in error messages
+    pub value: i32,
+}
+
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowDiagnostic {
+    pub row: usize, // one-indexed
+    pub cells: Vec<Cell>,
+    pub checksum: Option<i32>,
+    pub problem: Option<String>, // why there's no checksum, if the strategy can say
+}
+
+
+pub fn diagnose<T, C>(matrix: &[T], row_checksum: &C) -> Vec<RowDiagnostic>
+where
+    T: AsRef<[i32]>,
+    C: RowChecksum + ?Sized,
+{
+    matrix
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let row = row.as_ref();
+            let checksum = row_checksum.checksum(row);
+            RowDiagnostic {
+                row: i + 1,
+                cells: row_checksum
+                    .cells(row)
+                    .into_iter()
+                    .map(|(role, index)| Cell {
+                        role,
+                        column: index + 1,
+                        value: row[index],
+                    })
+                    .collect(),
+                checksum,
+                problem: match checksum {
+                    Some(_) => None,
+                    None => row_checksum.explain(row),
+                },
+            }
+        })
+        .collect()
+}
+
+
+// the sum of every row's checksum (None if any row doesn't have one, or the sum doesn't fit in an
+// i32)
+pub fn total(rows: &[RowDiagnostic]) -> Option<i32> {
+    rows.iter().try_fold(0i32, |total, row| total.checked_add(row.checksum?))
+}
+
+
+// write the rows as a table, followed by the total
+pub fn write_table<W: Write>(rows: &[RowDiagnostic], out: &mut W) -> io::Result<()> {
+    let mut lines = vec![[String::from("Row"), String::from("Checksum"), String::from("Cells")]];
+    lines.extend(rows.iter().map(|row| {
+        let checksum = match row.checksum {
+            Some(checksum) => checksum.to_string(),
+            None => String::from("-"),
+        };
+        let cells: Vec<String> = row.cells
+            .iter()
+            .map(|cell| format!("{} {} (column {})", cell.role, cell.value, cell.column))
+            .collect();
+        let detail = match row.problem {
+            Some(ref problem) => problem.clone(),
+            None if row.checksum.is_none() => String::from("no checksum"),
+            None => cells.join(", "),
+        };
+        [row.row.to_string(), checksum, detail]
+    }));
+
+    let row_width = lines.iter().map(|line| line[0].len()).max().unwrap_or(0);
+    let checksum_width = lines.iter().map(|line| line[1].len()).max().unwrap_or(0);
+    for line in lines.iter() {
+        writeln!(
+            out,
+            "{:<row_width$}  {:<checksum_width$}  {}",
+            line[0],
+            line[1],
+            line[2],
+            row_width = row_width,
+            checksum_width = checksum_width
+        )?;
+    }
+
+    match total(rows) {
+        Some(total) => writeln!(out, "Total {}", total),
+        None if rows.iter().any(|row| row.checksum.is_none()) => {
+            writeln!(out, "Total - (not every row has a checksum)")
+        }
+        None => writeln!(out, "Total - (doesn't fit in 32 bits)"),
+    }
+}
+
+
+// write the rows and total as a JSON object, labeled with the name of the checksum strategy
+pub fn write_json<W>(strategy: &str, rows: &[RowDiagnostic], out: &mut W) -> io::Result<()>
+where
+    W: Write,
+{
+    let total = match total(rows) {
+        Some(total) => total.to_string(),
+        None => String::from("null"),
+    };
+    writeln!(out, "{{\"strategy\": \"{}\", \"total\": {}, \"rows\": [", escape(strategy), total)?;
+
+    for (i, row) in rows.iter().enumerate() {
+        let cells: Vec<String> = row.cells
+            .iter()
+            .map(|cell| {
+                format!(
+                    "{{\"role\": \"{}\", \"column\": {}, \"value\": {}}}",
+                    escape(cell.role),
+                    cell.column,
+                    cell.value
+                )
+            })
+            .collect();
+        let checksum = match row.checksum {
+            Some(checksum) => checksum.to_string(),
+            None => String::from("null"),
+        };
+
+        write!(
+            out,
+            "  {{\"row\": {}, \"checksum\": {}, \"cells\": [{}]",
+            row.row,
+            checksum,
+            cells.join(", ")
+        )?;
+        if let Some(ref problem) = row.problem {
+            write!(out, ", \"problem\": \"{}\"", escape(problem))?;
+        }
+        writeln!(out, "}}{}", if i + 1 < rows.len() { "," } else { "" })?;
+    }
+
+    write!(out, "]}}")
+}
+
+
+// escape a string for use inside a JSON string
+fn escape(s: &str) -> String {
+    let mut escaped = String::new();
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use day02::{Difference, Quotient};
+
+    fn matrix() -> Vec<Vec<i32>> {
+        vec![vec![5, 9, 2, 8], vec![9, 5, 7]]
+    }
+
+    #[test]
+    fn diagnose_rows() {
+        let rows = diagnose(&matrix(), &Difference);
+        assert_eq!(
+            rows[0],
+            RowDiagnostic {
+                row: 1,
+                cells: vec![
+                    Cell { role: "min", column: 3, value: 2 },
+                    Cell { role: "max", column: 2, value: 9 },
+                ],
+                checksum: Some(7),
+                problem: None,
+            }
+        );
+        assert_eq!(total(&rows), Some(11));
+        assert_eq!(total(&diagnose(&matrix(), &Quotient)), None);
+    }
+
+    #[test]
+    fn table() {
+        let mut out = Vec::new();
+        write_table(&diagnose(&matrix(), &Quotient), &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Row  Checksum  Cells\n\
+             1    4         dividend 8 (column 4), divisor 2 (column 3)\n\
+             2    -         no two values divide evenly\n\
+             Total - (not every row has a checksum)\n"
+        );
+    }
+
+    #[test]
+    fn total_overflow() {
+        let rows = diagnose(&[vec![0, i32::MAX], vec![0, 1]], &Difference);
+        assert_eq!(total(&rows), None);
+
+        let mut out = Vec::new();
+        write_table(&rows, &mut out).unwrap();
+        let table = String::from_utf8(out).unwrap();
+        assert!(table.ends_with("Total - (doesn't fit in 32 bits)\n"), "{}", table);
+
+        let mut out = Vec::new();
+        write_json("difference", &rows, &mut out).unwrap();
+        assert!(String::from_utf8(out).unwrap().contains("\"total\": null"));
+    }
+
+    #[test]
+    fn json() {
+        let mut out = Vec::new();
+        write_json("quotient", &diagnose(&matrix(), &Quotient), &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"strategy\": \"quotient\", \"total\": null, \"rows\": [\n  \
+             {\"row\": 1, \"checksum\": 4, \"cells\": [\
+                 {\"role\": \"dividend\", \"column\": 4, \"value\": 8}, \
+                 {\"role\": \"divisor\", \"column\": 3, \"value\": 2}]},\n  \
+             {\"row\": 2, \"checksum\": null, \"cells\": [], \
+                 \"problem\": \"no two values divide evenly\"}\n\
+             ]}"
+        );
+        assert_eq!(escape("a \"b\"\n"), "a \\\"b\\\"\\u000a");
+    }
+}
@@ -0,0 +1,566 @@
+// http://adventofcode.com/2017/day/2
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+
+use {Error, Result, Solution};
+use error::{column_of, parse_token};
+
+pub mod diagnostics;
+pub mod parallel;
+
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Vec<i32>>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    // one row of the spreadsheet per line, cells separated by commas or whitespace
+    fn parse(input: &str) -> Result<Vec<Vec<i32>>> {
+        parse_spreadsheet(input, Separator::detect(input))
+    }
+
+    fn part_one(matrix: &Vec<Vec<i32>>) -> Result<i32> {
+        matrix_checksum(matrix, &Difference)
+    }
+
+    fn part_two(matrix: &Vec<Vec<i32>>) -> Result<i32> {
+        matrix_checksum(matrix, &Quotient)
+    }
+}
+
+
+// what the cells of each row of a spreadsheet are separated by
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Separator {
+    Tab,
+    Comma,
+    Whitespace, // any amount of spaces and tabs
+}
+
+impl Separator {
+    // commas if there are any in the input, otherwise whitespace (which includes tabs)
+    pub fn detect(input: &str) -> Separator {
+        match input.contains(',') {
+            true => Separator::Comma,
+            false => Separator::Whitespace,
+        }
+    }
+
+    // the non-blank cells of a line, trimmed of any surrounding whitespace
+    fn cells(self, line: &str) -> Vec<&str> {
+        let cells: Vec<&str> = match self {
+            Separator::Tab => line.split('\t').collect(),
+            Separator::Comma => line.split(',').collect(),
+            Separator::Whitespace => line.split_whitespace().collect(),
+        };
+        cells.into_iter().map(str::trim).filter(|cell| !cell.is_empty()).collect()
+    }
+}
+
+
+// one row per line, skipping blank lines; rows can have any number of cells
+pub fn parse_spreadsheet(input: &str, separator: Separator) -> Result<Vec<Vec<i32>>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|&(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| parse_row(line, i + 1, separator))
+        .collect()
+}
+
+fn parse_row(line: &str, line_number: usize, separator: Separator) -> Result<Vec<i32>> {
+    separator
+        .cells(line)
+        .into_iter()
+        .map(|cell| parse_token(cell, line_number, column_of(line, cell), "a number"))
+        .collect()
+}
+
+
+// a way of boiling a row of the spreadsheet down to a single number (None if the row doesn't
+// have one)
+pub trait RowChecksum {
+    fn checksum(&self, row: &[i32]) -> Option<i32>;
+
+    // why a row has no checksum, if there's more to say than that it hasn't
+    fn explain(&self, _row: &[i32]) -> Option<String> {
+        None
+    }
+
+    // the cells the checksum was worked out from (by index into the row), each with a word for
+    // the part it played, for showing how a checksum came about
+    fn cells(&self, _row: &[i32]) -> Vec<(&'static str, usize)> {
+        Vec::new()
+    }
+}
+
+// any function of a row will do, too
+impl<F: Fn(&[i32]) -> Option<i32>> RowChecksum for F {
+    fn checksum(&self, row: &[i32]) -> Option<i32> {
+        self(row)
+    }
+}
+
+
+// part one's checksum: see row_difference
+pub struct Difference;
+
+impl RowChecksum for Difference {
+    fn checksum(&self, row: &[i32]) -> Option<i32> {
+        row_difference(row)
+    }
+
+    fn explain(&self, row: &[i32]) -> Option<String> {
+        match row.is_empty() {
+            true => None,
+            false => Some(String::from("the difference doesn't fit in 32 bits")),
+        }
+    }
+
+    fn cells(&self, row: &[i32]) -> Vec<(&'static str, usize)> {
+        let indexes = 0..row.len();
+        match (indexes.clone().min_by_key(|&i| row[i]), indexes.max_by_key(|&i| row[i])) {
+            (Some(min), Some(max)) => vec![("min", min), ("max", max)],
+            _ => Vec::new(),
+        }
+    }
+}
+
+
+// part two's checksum: see row_quotient
+pub struct Quotient;
+
+impl RowChecksum for Quotient {
+    fn checksum(&self, row: &[i32]) -> Option<i32> {
+        row_quotient(row)
+    }
+
+    fn explain(&self, row: &[i32]) -> Option<String> {
+        let pairs = divisible_pairs(row);
+        match pairs.len() {
+            0 => Some(String::from("no two values divide evenly")),
+            1 => None,
+            n => {
+                let pairs: Vec<String> = pairs.iter().map(ToString::to_string).collect();
+                Some(format!("{} pairs divide evenly: {}", n, pairs.join(", ")))
+            }
+        }
+    }
+
+    fn cells(&self, row: &[i32]) -> Vec<(&'static str, usize)> {
+        match divisible_pairs(row).as_slice() {
+            [pair] => vec![("dividend", pair.dividend_index), ("divisor", pair.divisor_index)],
+            _ => Vec::new(),
+        }
+    }
+}
+
+
+// like Difference, but only over the middle given percent of the row's values once they're
+// sorted, so a few outliers don't swamp it (50 gives the interquartile range)
+pub struct MiddleRange {
+    pub percent: usize,
+}
+
+impl RowChecksum for MiddleRange {
+    fn checksum(&self, row: &[i32]) -> Option<i32> {
+        match self.cells(row).as_slice() {
+            &[(_, low), (_, high)] => row[high].checked_sub(row[low]),
+            _ => None,
+        }
+    }
+
+    fn explain(&self, row: &[i32]) -> Option<String> {
+        match row.is_empty() {
+            true => None,
+            false => Some(String::from("the range doesn't fit in 32 bits")),
+        }
+    }
+
+    fn cells(&self, row: &[i32]) -> Vec<(&'static str, usize)> {
+        if row.is_empty() {
+            return Vec::new();
+        }
+        let mut sorted: Vec<usize> = (0..row.len()).collect();
+        sorted.sort_by_key(|&i| row[i]);
+
+        // how many values to drop from each end (never so many that the low end passes the high
+        // one, which an even-length row at 0% would otherwise do)
+        let trim = sorted.len() * (100 - self.percent.min(100)) / 200;
+        let trim = trim.min((sorted.len() - 1) / 2);
+        vec![("low", sorted[trim]), ("high", sorted[sorted.len() - 1 - trim])]
+    }
+}
+
+
+// sum of the quotients of every pair of values in the row where one evenly divides the other
+pub struct QuotientSum;
+
+impl RowChecksum for QuotientSum {
+    fn checksum(&self, row: &[i32]) -> Option<i32> {
+        divisible_pairs(row).iter().try_fold(0i32, |sum, pair| sum.checked_add(pair.quotient))
+    }
+
+    fn explain(&self, _row: &[i32]) -> Option<String> {
+        Some(String::from("the sum doesn't fit in 32 bits"))
+    }
+}
+
+
+// greatest common divisor of all the values in the row (None if the row is empty, or it's 2^31,
+// which only a row of nothing but -2^31 and zeros has)
+pub struct Gcd;
+
+impl RowChecksum for Gcd {
+    fn checksum(&self, row: &[i32]) -> Option<i32> {
+        let divisor = row.iter().map(|value| value.unsigned_abs()).fold(None, |divisor, value| {
+            match divisor {
+                Some(divisor) => Some(gcd(divisor, value)),
+                None => Some(value),
+            }
+        })?;
+        i32::try_from(divisor).ok()
+    }
+
+    fn explain(&self, row: &[i32]) -> Option<String> {
+        match row.is_empty() {
+            true => None,
+            false => Some(String::from("the greatest common divisor doesn't fit in 32 bits")),
+        }
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+
+// the names of every checksum that can be picked by name
+pub const STRATEGIES: &[&str] = &["difference", "quotient", "middle-range", "quotient-sum", "gcd"];
+
+// a checksum picked by name (see STRATEGIES)
+pub fn strategy(name: &str) -> Option<Box<dyn RowChecksum + Sync>> {
+    match name {
+        "difference" => Some(Box::new(Difference)),
+        "quotient" => Some(Box::new(Quotient)),
+        "middle-range" => Some(Box::new(MiddleRange { percent: 50 })),
+        "quotient-sum" => Some(Box::new(QuotientSum)),
+        "gcd" => Some(Box::new(Gcd)),
+        _ => None,
+    }
+}
+
+
+// difference between the largest and smallest values in the row (None if the row is empty, or
+// the difference doesn't fit in an i32)
+pub fn row_difference(row: &[i32]) -> Option<i32> {
+    row.iter().max()?.checked_sub(*row.iter().min()?)
+}
+
+
+// quotient of the only two values in the row where one evenly divides the other (None if there
+// aren't any such values, or there's more than one pair of them)
+pub fn row_quotient(row: &[i32]) -> Option<i32> {
+    match divisible_pairs(row).as_slice() {
+        [pair] => Some(pair.quotient),
+        _ => None,
+    }
+}
+
+
+// two cells of a row where one evenly divides the other (indexes count from 0)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DivisiblePair {
+    pub dividend: i32,
+    pub dividend_index: usize,
+    pub divisor: i32,
+    pub divisor_index: usize,
+    pub quotient: i32,
+}
+
+impl fmt::Display for DivisiblePair {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} / {} = {}", self.dividend, self.divisor, self.quotient)
+    }
+}
+
+
+// every pair of cells in the row where the one with the smaller magnitude evenly divides the
+// other, ordered by where the dividend and then the divisor are in the row. Signs don't matter
+// for divisibility, but are kept in the quotient (-8 and 2 give -4). Zeros are never part of a
+// pair: nothing can be divided by zero, and zero's smaller in magnitude than any divisor of it.
+// Cells of equal magnitude aren't a pair either, so duplicates don't count. (The only quotient
+// that can't fit in an i32, i32::MIN / -1, is left out too.)
+pub fn divisible_pairs(row: &[i32]) -> Vec<DivisiblePair> {
+    // where each magnitude appears in the row, and the distinct magnitudes in increasing order
+    let mut positions: HashMap<u32, Vec<usize>> = HashMap::new();
+    for (index, value) in row.iter().enumerate() {
+        if *value != 0 {
+            positions.entry(value.unsigned_abs()).or_default().push(index);
+        }
+    }
+    let mut magnitudes: Vec<u32> = positions.keys().cloned().collect();
+    magnitudes.sort();
+
+    let mut pairs = Vec::new();
+    for (i, &divisor) in magnitudes.iter().enumerate() {
+        let larger = &magnitudes[i + 1..];
+        let largest = match larger.last() {
+            Some(&largest) => largest,
+            None => break,
+        };
+
+        // either step through the multiples of the divisor (a sieve) or try each larger value,
+        // whichever means fewer checks: small divisors of rows with a few huge values are
+        // cheaper to test directly
+        let multiples: Vec<u32> = if ((largest / divisor) as usize) < larger.len() {
+            (divisor * 2..=largest)
+                .step_by(divisor as usize)
+                .filter(|multiple| positions.contains_key(multiple))
+                .collect()
+        } else {
+            larger.iter().cloned().filter(|value| value % divisor == 0).collect()
+        };
+
+        for multiple in multiples {
+            for &dividend_index in positions[&multiple].iter() {
+                for &divisor_index in positions[&divisor].iter() {
+                    let (dividend, divisor) = (row[dividend_index], row[divisor_index]);
+                    if let Some(quotient) = dividend.checked_div(divisor) {
+                        pairs.push(DivisiblePair {
+                            dividend,
+                            dividend_index,
+                            divisor,
+                            divisor_index,
+                            quotient,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    pairs.sort_by_key(|pair| (pair.dividend_index, pair.divisor_index));
+    pairs
+}
+
+
+// sum of the checksums of every row in the matrix
+pub fn matrix_checksum<T, C>(matrix: &[T], row_checksum: &C) -> Result<i32>
+where
+    T: AsRef<[i32]>,
+    C: RowChecksum + ?Sized,
+{
+    matrix.iter().enumerate().try_fold(0i32, |total, (i, row)| {
+        let checksum = checked_checksum(row_checksum, row.as_ref(), i + 1)?;
+        total.checked_add(checksum).ok_or_else(|| {
+            Error::unsolvable("the checksum doesn't fit in 32 bits")
+        })
+    })
+}
+
+// a row's checksum, or an error saying why it hasn't got one
+fn checked_checksum<C>(row_checksum: &C, row: &[i32], row_number: usize) -> Result<i32>
+where
+    C: RowChecksum + ?Sized,
+{
+    row_checksum.checksum(row).ok_or_else(|| {
+        let message = match row_checksum.explain(row) {
+            Some(reason) => format!("row {} has no checksum: {}", row_number, reason),
+            None => format!("row {} has no checksum", row_number),
+        };
+        Error::unsolvable(message)
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn row_difference_overflow() {
+        assert_eq!(row_difference(&[-1, i32::MAX]), None);
+        assert_eq!(
+            Day02::part_one(&vec![vec![1, 2], vec![-1, i32::MAX]]).unwrap_err().to_string(),
+            "row 2 has no checksum: the difference doesn't fit in 32 bits"
+        );
+    }
+
+    #[test]
+    fn row_difference_1() {
+        assert_eq!(row_difference(&[5, 1, 9, 5]), Some(8));
+    }
+
+    #[test]
+    fn row_difference_2() {
+        assert_eq!(row_difference(&[7, 5, 3]), Some(4));
+    }
+
+    #[test]
+    fn row_difference_3() {
+        assert_eq!(row_difference(&[2, 4, 6, 8]), Some(6));
+    }
+
+    #[test]
+    fn row_quotient_1() {
+        assert_eq!(row_quotient(&[5, 9, 2, 8]), Some(4));
+    }
+
+    #[test]
+    fn row_quotient_2() {
+        assert_eq!(row_quotient(&[9, 4, 7, 3]), Some(3));
+    }
+
+    #[test]
+    fn row_quotient_3() {
+        assert_eq!(row_quotient(&[3, 8, 6, 5]), Some(2));
+    }
+
+    #[test]
+    fn divisible_pairs_with_zeros_and_negatives() {
+        let quotients = |row: &[i32]| -> Vec<(usize, usize, i32)> {
+            divisible_pairs(row)
+                .iter()
+                .map(|pair| (pair.dividend_index, pair.divisor_index, pair.quotient))
+                .collect()
+        };
+        assert_eq!(quotients(&[0, 5, 0, 7]), vec![]);
+        assert_eq!(quotients(&[0, 3, 12]), vec![(2, 1, 4)]);
+        assert_eq!(quotients(&[-8, 2, 5]), vec![(0, 1, -4)]);
+        assert_eq!(quotients(&[-9, -3]), vec![(0, 1, 3)]);
+        assert_eq!(quotients(&[4, 4, -4]), vec![]);
+        assert_eq!(quotients(&[2, 6, 2]), vec![(1, 0, 3), (1, 2, 3)]);
+        assert_eq!(quotients(&[i32::MIN, -1, 2]), vec![(0, 2, i32::MIN / 2), (2, 1, -2)]);
+        assert_eq!(row_quotient(&[0, 5, 0, 7]), None);
+        assert_eq!(row_quotient(&[-8, 2, 5]), Some(-4));
+    }
+
+    // both ways of finding multiples (the sieve and checking each value) against every pair
+    #[test]
+    fn divisible_pairs_match_brute_force() {
+        let mut state: u32 = 17;
+        for length in 0..60 {
+            let row: Vec<i32> = (0..length)
+                .map(|_| {
+                    state = state.wrapping_mul(1103515245).wrapping_add(12345);
+                    let range = if length % 2 == 0 { 50 } else { 100_000 };
+                    ((state >> 8) % range) as i32 - range as i32 / 4
+                })
+                .collect();
+
+            let mut expected = Vec::new();
+            for (a, &dividend) in row.iter().enumerate() {
+                for (b, &divisor) in row.iter().enumerate() {
+                    let divides = divisor != 0 && dividend % divisor == 0;
+                    if divides && dividend.abs() > divisor.abs() {
+                        expected.push((a, b, dividend / divisor));
+                    }
+                }
+            }
+            let found: Vec<(usize, usize, i32)> = divisible_pairs(&row)
+                .iter()
+                .map(|pair| (pair.dividend_index, pair.divisor_index, pair.quotient))
+                .collect();
+            assert_eq!(found, expected, "{:?}", row);
+        }
+    }
+
+    #[test]
+    fn strategies() {
+        let row = [3, 12, 6, 100, 9, 1];
+        assert_eq!(MiddleRange { percent: 50 }.checksum(&row), Some(12 - 3));
+        assert_eq!(MiddleRange { percent: 100 }.checksum(&row), Some(99));
+        assert_eq!(MiddleRange { percent: 0 }.checksum(&[4, 1, 9]), Some(0));
+        assert_eq!(MiddleRange { percent: 0 }.checksum(&[1, 5]), Some(4));
+        assert_eq!(MiddleRange { percent: 0 }.checksum(&[8, 1, 9, 4]), Some(4));
+        assert_eq!(QuotientSum.checksum(&row), Some(4 + 2 + 3 + 3 + 12 + 6 + 100 + 9 + 2));
+        assert_eq!(Gcd.checksum(&[12, -18, 30]), Some(6));
+        assert_eq!(Gcd.checksum(&[]), None);
+
+        let matrix = vec![vec![2, 4], vec![3, 9, 5]];
+        for &name in STRATEGIES.iter() {
+            assert!(matrix_checksum(&matrix, &*strategy(name).unwrap()).is_ok(), "{}", name);
+        }
+        assert_eq!(matrix_checksum(&matrix, &|row: &[i32]| Some(row.len() as i32)).unwrap(), 5);
+        assert!(strategy("nope").is_none());
+    }
+
+    #[test]
+    fn strategies_overflow() {
+        assert_eq!(MiddleRange { percent: 100 }.checksum(&[i32::MIN, 1]), None);
+        assert_eq!(QuotientSum.checksum(&[i32::MAX, 1, 2147483646]), None);
+        assert_eq!(Gcd.checksum(&[i32::MIN, 4]), Some(4));
+        assert_eq!(Gcd.checksum(&[i32::MIN, 0]), None);
+
+        let matrix = vec![vec![i32::MAX, 1, 2147483646]];
+        assert_eq!(
+            matrix_checksum(&matrix, &QuotientSum).unwrap_err().to_string(),
+            "row 1 has no checksum: the sum doesn't fit in 32 bits"
+        );
+    }
+
+    #[test]
+    fn part_one() {
+        let matrix = Day02::parse("5 1 9 5\n7 5 3\n2 4 6 8\n").unwrap();
+        assert_eq!(Day02::part_one(&matrix).unwrap(), 18);
+    }
+
+    #[test]
+    fn part_two() {
+        let matrix = Day02::parse("5 9 2 8\n9 4 7 3\n3 8 6 5\n").unwrap();
+        assert_eq!(Day02::part_two(&matrix).unwrap(), 9);
+    }
+
+    #[test]
+    fn bad_cell() {
+        let error = Day02::parse("5 9 2 8\n9 4 x 3\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 5: expected a number, found \"x\"");
+    }
+
+    #[test]
+    fn separators() {
+        let expected = vec![vec![5, 1, 9, 5], vec![7, 5, 3], vec![2, 4, 6, 8]];
+        for input in [
+            "5\t1\t9\t5\n7\t5\t3\n2\t4\t6\t8\n",
+            "5,1,9,5\r\n7, 5, 3\r\n\r\n2,4,6,8,\r\n",
+            "\n  5 1  9 5\n7 5 3\n\n\n2 4\t6 8",
+        ].iter()
+        {
+            assert_eq!(Day02::parse(input).unwrap(), expected);
+        }
+
+        let input = "5\t1\t9 5\n";
+        let matrix = parse_spreadsheet(input, Separator::Whitespace).unwrap();
+        assert_eq!(matrix, vec![vec![5, 1, 9, 5]]);
+        let error = parse_spreadsheet(input, Separator::Tab).unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 5: expected a number, found \"9 5\"");
+    }
+
+    #[test]
+    fn overflow() {
+        let matrix = vec![vec![0, i32::MAX], vec![0, 1]];
+        assert!(matrix_checksum(&matrix, &Difference).is_err());
+    }
+
+    #[test]
+    fn no_divisible_pair() {
+        let matrix = Day02::parse("5 9 2 8\n9 5 7\n").unwrap();
+        assert_eq!(
+            Day02::part_two(&matrix).unwrap_err().to_string(),
+            "row 2 has no checksum: no two values divide evenly"
+        );
+        let matrix = Day02::parse("5 9 2 8\n9 3 6\n").unwrap();
+        assert_eq!(
+            Day02::part_two(&matrix).unwrap_err().to_string(),
+            "row 2 has no checksum: 2 pairs divide evenly: 9 / 3 = 3, 6 / 3 = 2"
+        );
+    }
+}
@@ -0,0 +1,229 @@
+// Checksums of spreadsheets too big to want in memory all at once: the calling thread reads the
+// input a batch of lines at a time, while a pool of worker threads parses the batches and works
+// out their rows' checksums.
+
+use std::io::BufRead;
+use std::mem;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver};
+use std::sync::Mutex;
+use std::thread;
+
+use {Error, Result};
+use super::{checked_checksum, parse_row, RowChecksum, Separator};
+
+
+// how many lines of input are handed to a worker at a time
+const BATCH_LINES: usize = 4096;
+
+
+// the sum of every row's checksum, like matrix_checksum, but reading the rows as it goes and
+// spreading the work over the given number of threads. The separator is detected from the first
+// batch of lines if it isn't given. The total is an i64, since millions of rows can easily add up
+// to more than an i32 holds. If anything goes wrong, the error is the one nearest the start of
+// the input (as matrix_checksum would have reported).
+pub fn parallel_checksum<R, C>(
+    mut input: R,
+    separator: Option<Separator>,
+    row_checksum: &C,
+    threads: usize,
+) -> Result<i64>
+where
+    R: BufRead,
+    C: RowChecksum + Sync + ?Sized,
+{
+    let mut reader = BatchReader::new();
+    let first = reader.read(&mut input)?;
+    let separator = separator.unwrap_or_else(|| Separator::detect(&first.lines.join("\n")));
+
+    let threads = threads.max(1);
+    let (sender, receiver) = sync_channel(threads * 2);
+    let receiver = Mutex::new(receiver);
+    let failed = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| scope.spawn(|| work(&receiver, separator, row_checksum, &failed)))
+            .collect();
+
+        // keep the workers fed until the input runs out or one of them finds a problem (the
+        // batches already sent still get finished, in case one of them has an earlier problem)
+        let mut batch = first;
+        let mut read_error = None;
+        while !batch.lines.is_empty() && !failed.load(Ordering::Relaxed) {
+            if sender.send(batch).is_err() {
+                break;
+            }
+            batch = match reader.read(&mut input) {
+                Ok(batch) => batch,
+                Err(e) => {
+                    read_error = Some((reader.line, e));
+                    break;
+                }
+            };
+        }
+        drop(sender);
+
+        let mut total = 0;
+        let mut first_error = read_error;
+        for worker in workers {
+            let (sum, error) = worker.join().expect("checksum worker panicked");
+            total += sum;
+            first_error = earliest(first_error, error);
+        }
+        match first_error {
+            Some((_, e)) => Err(e),
+            None => Ok(total),
+        }
+    })
+}
+
+
+// a run of consecutive lines of input
+struct Batch {
+    first_line: usize, // one-indexed
+    first_row: usize,  // the row number of the first non-blank line, also one-indexed
+    lines: Vec<String>,
+}
+
+
+// splits the input into batches, keeping count of lines and rows (non-blank lines) so far
+struct BatchReader {
+    line: usize,
+    row: usize,
+}
+
+impl BatchReader {
+    fn new() -> BatchReader {
+        BatchReader { line: 1, row: 1 }
+    }
+
+    // the next batch of lines (which is empty at the end of the input)
+    fn read<R: BufRead>(&mut self, input: &mut R) -> Result<Batch> {
+        let mut batch = Batch {
+            first_line: self.line,
+            first_row: self.row,
+            lines: Vec::with_capacity(BATCH_LINES),
+        };
+        let mut line = String::new();
+        while batch.lines.len() < BATCH_LINES && input.read_line(&mut line)? > 0 {
+            self.line += 1;
+            if !line.trim().is_empty() {
+                self.row += 1;
+            }
+            batch.lines.push(mem::take(&mut line));
+        }
+        Ok(batch)
+    }
+}
+
+
+// sum the checksums of batches until there are none left, returning the total and the earliest
+// problem found (by line number)
+fn work<C>(
+    batches: &Mutex<Receiver<Batch>>,
+    separator: Separator,
+    row_checksum: &C,
+    failed: &AtomicBool,
+) -> (i64, Option<(usize, Error)>)
+where
+    C: RowChecksum + ?Sized,
+{
+    let mut total = 0;
+    let mut first_error = None;
+    loop {
+        // (the lock's released as soon as there's a batch, so other workers can wait for the next)
+        let next = batches.lock().unwrap().recv();
+        let batch = match next {
+            Ok(batch) => batch,
+            Err(_) => return (total, first_error),
+        };
+        match batch_checksum(&batch, separator, row_checksum) {
+            Ok(sum) => total += sum,
+            Err(error) => {
+                failed.store(true, Ordering::Relaxed);
+                first_error = earliest(first_error, Some(error));
+            }
+        }
+    }
+}
+
+
+fn batch_checksum<C>(
+    batch: &Batch,
+    separator: Separator,
+    row_checksum: &C,
+) -> ::std::result::Result<i64, (usize, Error)>
+where
+    C: RowChecksum + ?Sized,
+{
+    let mut total = 0;
+    let mut row_number = batch.first_row;
+    for (i, line) in batch.lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line_number = batch.first_line + i;
+        let checksum = parse_row(line.trim_end_matches(&['\r', '\n'][..]), line_number, separator)
+            .and_then(|row| checked_checksum(row_checksum, &row, row_number))
+            .map_err(|e| (line_number, e))?;
+        total += i64::from(checksum);
+        row_number += 1;
+    }
+    Ok(total)
+}
+
+
+fn earliest(a: Option<(usize, Error)>, b: Option<(usize, Error)>) -> Option<(usize, Error)> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if b.0 < a.0 { b } else { a }),
+        (a, b) => a.or(b),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use day02::{matrix_checksum, parse_spreadsheet, Difference, Quotient};
+
+    // enough rows for several batches, with blank lines in between
+    fn spreadsheet(rows: usize) -> String {
+        (0..rows)
+            .map(|i| match i % 7 {
+                6 => String::from("\n"),
+                _ => {
+                    let k = i % 13 + 1;
+                    format!("{}\t{}\t{}\t{}\n", k, 2 * k, i % 5 + 30, i % 11 + 40)
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matches_serial() {
+        let input = spreadsheet(3 * BATCH_LINES + 100);
+        let matrix = parse_spreadsheet(&input, Separator::Tab).unwrap();
+        for threads in 1..5 {
+            assert_eq!(
+                parallel_checksum(input.as_bytes(), None, &Difference, threads).unwrap(),
+                i64::from(matrix_checksum(&matrix, &Difference).unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn earliest_error() {
+        // (plenty of the later rows don't have exactly one divisible pair either)
+        let input = String::from("2 4\n\n3 9\n7 9\n") + &spreadsheet(3 * BATCH_LINES);
+        let error = parallel_checksum(input.as_bytes(), None, &Quotient, 4).unwrap_err();
+        assert_eq!(error.to_string(), "row 3 has no checksum: no two values divide evenly");
+
+        let input = spreadsheet(BATCH_LINES + 10) + "1 x 3\n";
+        let error = parallel_checksum(input.as_bytes(), None, &Difference, 3).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("line {}, column 3: expected a number, found \"x\"", BATCH_LINES + 11)
+        );
+    }
+}
@@ -0,0 +1,809 @@
+// http://adventofcode.com/2017/day/3
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+
+use {Error, Result, Solution};
+use error::{abbreviate, parse_token, tokens};
+
+pub mod render;
+
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = u32;
+    type PartOne = u64;
+    type PartTwo = u32;
+
+    // a single square number, which is also the target value for part two
+    fn parse(input: &str) -> Result<u32> {
+        let mut found = input
+            .lines()
+            .enumerate()
+            .flat_map(|(i, line)| tokens(line).map(move |(column, token)| (i + 1, column, token)));
+
+        let number = match found.next() {
+            Some((line, column, token)) => {
+                match parse_token(token, line, column, "a square number")? {
+                    0 => return Err(Error::parse(line, column, "square numbers start at 1")),
+                    number => number,
+                }
+            }
+            None => return Err(Error::parse(1, 1, "expected a square number")),
+        };
+
+        match found.next() {
+            Some((line, column, token)) => {
+                let message = format!("unexpected \"{}\" after the square number", abbreviate(token));
+                Err(Error::parse(line, column, message))
+            }
+            None => Ok(number),
+        }
+    }
+
+    // distance from the specified index to the center of the spiral
+    fn part_one(index: &u32) -> Result<u64> {
+        let access_port = Point { x: 0, y: 0 };
+        let other_location = Point::from_index(u64::from(*index))
+            .ok_or_else(|| Error::unsolvable("square numbers start at 1"))?;
+        Ok(access_port.manhattan(&other_location))
+    }
+
+    // first value written to the spiral that is larger than the target value
+    fn part_two(target_value: &u32) -> Result<u32> {
+        let target = u128::from(*target_value);
+        Spiral::default()
+            .values()
+            .map(|(_, _, value)| value)
+            .find(|&value| value > target)
+            .and_then(|value| u32::try_from(value).ok())
+            .ok_or_else(|| {
+                Error::unsolvable(format!("no value larger than {} fits in 32 bits", target_value))
+            })
+    }
+}
+
+
+//  index reference:
+
+//  37  36  35  34  33  32  31
+//  38  17  16  15  14  13  30
+//  39  18   5   4   3  12  29
+//  40  19   6   1   2  11  28
+//  41  20   7   8   9  10  27
+//  42  21  22  23  24  25  26
+//  43  44  45  46  47  48  49
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    // location of the square with the given (one-indexed) number on the spiral, worked out
+    // directly from which ring and edge the square is on (using only integer arithmetic, so it's
+    // exact for every u64), or None for 0, which isn't on the spiral
+    pub fn from_index(index: u64) -> Option<Point> {
+        Some(Point::from_offset(index.checked_sub(1)?))
+    }
+
+    // the (one-indexed) number of the square at this location on the spiral, the inverse of
+    // from_index (None for points too far out for their number to fit in a u64)
+    pub fn to_index(&self) -> Option<u64> {
+        self.offset()?.checked_add(1)
+    }
+
+    // location of the square the given number of squares along the spiral from the center
+    fn from_offset(offset: u64) -> Point {
+        if offset == 0 {
+            return Point { x: 0, y: 0 };
+        }
+
+        let (ring, ring_end_point) = Point::get_ring_info(offset);
+        let (edge_id, edge_index) = Point::get_edge_info(offset, ring);
+        let k = ring as i64;
+        let offset = edge_index as i64;
+
+        match edge_id {
+            0 => Point { x: ring_end_point.x, y: -k + 1 + offset },
+            1 => Point { x: k - 1 - offset, y: k },
+            2 => Point { x: -k, y: k - 1 - offset },
+            3 => Point { x: -k + 1 + offset, y: ring_end_point.y },
+            _ => panic!("unexpected edge_id found"),
+        }
+    }
+
+    // how many squares along the spiral from the center this location is
+    fn offset(&self) -> Option<u64> {
+        let ring = self.x.unsigned_abs().max(self.y.unsigned_abs());
+        if ring == 0 {
+            return Some(0);
+        }
+        if ring > 1 << 31 {
+            return None;
+        }
+
+        let k = ring as i64;
+        let (edge_id, edge_index) = if self.x == k && self.y > -k {
+            (0, self.y + k - 1)
+        } else if self.y == k {
+            (1, k - 1 - self.x)
+        } else if self.x == -k {
+            (2, k - 1 - self.y)
+        } else {
+            (3, self.x + k - 1)
+        };
+
+        let edge_length = 2 * ring;
+        let ring_start_offset = (edge_length - 1) * (edge_length - 1);
+        ring_start_offset.checked_add(edge_id * edge_length + edge_index as u64)
+    }
+
+    // the ring containing the square that many squares from the center (the center is ring 0),
+    // and the point at its bottom right corner, where it ends
+    fn get_ring_info(offset: u64) -> (u64, Point) {
+        // the end of each ring is the square of an odd number [1, 9, 25, 49, ...], so ring k holds
+        // the offsets from (2k - 1)^2 up to (2k + 1)^2 - 1
+        let ring = offset.isqrt().div_ceil(2);
+        let k = ring as i64;
+        (ring, Point { x: k, y: -k })
+    }
+
+    // which edge of its ring the offset is on (0 for the right edge, counting counter-clockwise),
+    // and its position along that edge (zero-indexed)
+    fn get_edge_info(offset: u64, ring: u64) -> (u64, u64) {
+        let edge_length = 2 * ring;
+        let ring_index = offset - (edge_length - 1) * (edge_length - 1);
+        (ring_index / edge_length, ring_index % edge_length)
+    }
+
+    // this point turned a quarter turn counter-clockwise about the center, the given number of
+    // times (negating coordinates wraps, which leaves points too far out to number as they are)
+    fn rotate(&self, quarter_turns: u32) -> Point {
+        (0..quarter_turns % 4).fold(*self, |p, _| Point { x: p.y.wrapping_neg(), y: p.x })
+    }
+
+    // this point reflected in the x axis
+    fn reflect(&self) -> Point {
+        Point { x: self.x, y: self.y.wrapping_neg() }
+    }
+
+    pub fn manhattan(&self, other: &Point) -> u64 {
+        let dx = (self.x - other.x).unsigned_abs();
+        let dy = (self.y - other.y).unsigned_abs();
+        dx + dy
+    }
+
+    pub fn neighbors(&self) -> [Point; 8] {
+        [
+            Point {
+                x: self.x + 1,
+                y: self.y,
+            },
+
+            Point {
+                x: self.x + 1,
+                y: self.y + 1,
+            },
+            Point {
+                x: self.x,
+                y: self.y + 1,
+            },
+            Point {
+                x: self.x - 1,
+                y: self.y + 1,
+            },
+
+            Point {
+                x: self.x - 1,
+                y: self.y,
+            },
+
+            Point {
+                x: self.x - 1,
+                y: self.y - 1,
+            },
+            Point {
+                x: self.x,
+                y: self.y - 1,
+            },
+            Point {
+                x: self.x + 1,
+                y: self.y - 1,
+            },
+        ]
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+
+// which way a spiral turns as it winds outwards
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Winding {
+    CounterClockwise,
+    Clockwise,
+}
+
+
+// the shape of a spiral and how its squares are numbered: the puzzle's spiral (the default) winds
+// counter-clockwise, takes its first step to the right and numbers the center square 1
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spiral {
+    pub winding: Winding,
+    pub start: Direction, // the direction of the first step out from the center
+    pub origin: u64,      // the number of the center square
+}
+
+impl Spiral {
+    pub fn new(winding: Winding, start: Direction, origin: u64) -> Spiral {
+        Spiral { winding, start, origin }
+    }
+
+    // location of the square with the given number (None if it's numbered before the center)
+    pub fn point(&self, index: u64) -> Option<Point> {
+        let point = Point::from_offset(index.checked_sub(self.origin)?);
+        let point = match self.winding {
+            Winding::CounterClockwise => point,
+            Winding::Clockwise => point.reflect(),
+        };
+        Some(point.rotate(self.start.quarter_turns()))
+    }
+
+    // the number of the square at the given location (None if it doesn't fit in a u64)
+    pub fn index(&self, point: &Point) -> Option<u64> {
+        let point = point.rotate(4 - self.start.quarter_turns());
+        let point = match self.winding {
+            Winding::CounterClockwise => point,
+            Winding::Clockwise => point.reflect(),
+        };
+        self.origin.checked_add(point.offset()?)
+    }
+
+    // every square of the spiral in order, starting from the center, along with the value part two
+    // writes to it
+    pub fn values(&self) -> Values {
+        Values {
+            cursor: Cursor::with_spiral(*self),
+            squares_started: 0,
+        }
+    }
+}
+
+impl Default for Spiral {
+    fn default() -> Spiral {
+        Spiral::new(Winding::CounterClockwise, Direction::Right, 1)
+    }
+}
+
+
+type PointMatrix = HashMap<(i64, i64), u128>;
+
+
+// walks the spiral filling each square with the sum of its already-filled neighbors
+#[derive(Debug)]
+pub struct Cursor {
+    matrix: PointMatrix, // data structure holding the values computed by the cursor so far
+    spiral: Spiral, // the shape of the spiral being walked
+    index: Option<u64>, // the number of the square the cursor is on (None past u64::MAX)
+    direction: Direction, // the direction the cursor is "pointed" (by extension which edge it's on)
+    ring_index: u32, // position of cursor from beginning of current ring (zero indexed)
+    edge_index: u32, // position of cursor from beginning of current edge (zero-indexed)
+    edge_length: u32, // size of current edge
+    point: Point, // (x, y) coords of cursor in overall matrix (the initial value is at (0, 0))
+}
+
+impl Cursor {
+
+    // value reference:
+
+    // 147  142  133  122   59
+    // 304    5    4    2   57
+    // 330   10    1    1   54
+    // 351   11   23   25   26
+    // 362  747  806  880  931
+
+    // initialize new cursor with starting conditions described in the question
+    pub fn new() -> Cursor {
+        Cursor::with_spiral(Spiral::default())
+    }
+
+    // initialize new cursor on the second square of the given spiral (its values are the same as
+    // the puzzle's, since turning or flipping the spiral doesn't change any square's neighbors)
+    pub fn with_spiral(spiral: Spiral) -> Cursor {
+        let (dx, dy) = spiral.start.dxdy();
+        let mut matrix = PointMatrix::new();
+        matrix.insert((0, 0), 1); // odd case
+        matrix.insert((dx, dy), 1); // first space on a ring
+        Cursor {
+            matrix,
+            spiral,
+            index: spiral.origin.checked_add(1),
+            direction: spiral.start.turn(spiral.winding),
+            ring_index: 0,
+            edge_index: 0,
+            edge_length: 2,
+            point: Point { x: dx, y: dy },
+        }
+    }
+
+    // the number and location of the square the cursor is on (there's no number once they go
+    // past u64::MAX)
+    pub fn index(&self) -> Option<u64> {
+        self.index
+    }
+
+    pub fn point(&self) -> Point {
+        self.point
+    }
+
+    // update state of cursor to represent the next state we get to when walking the spiral, and
+    // return the value stored there (or None once the values no longer fit in a u128)
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<u128> {
+
+        // EDGE_INDEX
+        let (next_edge_index, is_new_edge) = if self.edge_index == self.edge_length - 1 {
+            (0, true)
+        } else {
+            (self.edge_index + 1, false)
+        };
+
+        // RING_INDEX
+        let (next_ring_index, is_new_ring) = if self.ring_index == (self.edge_length * 4) - 1 {
+            (0, true)
+        } else {
+            (self.ring_index + 1, false)
+        };
+
+        // EDGE_LENGTH
+        let next_edge_length = if is_new_ring {
+            self.edge_length + 2
+        } else {
+            self.edge_length
+        };
+
+        // DIRECTION
+        let is_time_to_turn = (is_new_edge && !is_new_ring) // a turn within an existing ring
+                || (self.ring_index == 0 && self.direction == self.spiral.start); // a turn after starting a new ring
+        let next_direction = if is_time_to_turn {
+            self.direction.turn(self.spiral.winding)
+        } else {
+            self.direction
+        };
+
+        // POINT
+        let (dx, dy) = next_direction.dxdy();
+        let next_point = Point {
+            x: self.point.x + dx,
+            y: self.point.y + dy,
+        };
+
+        // VALUE
+        let next_value = self.compute_value(&next_point)?;
+
+        // update cursor's attributes to represent the next state
+        self.matrix.insert((next_point.x, next_point.y), next_value);
+        self.index = self.index.and_then(|index| index.checked_add(1));
+        self.direction = next_direction;
+        self.ring_index = next_ring_index;
+        self.edge_index = next_edge_index;
+        self.edge_length = next_edge_length;
+        self.point = next_point;
+
+        // return the value at the next state
+        Some(next_value)
+    }
+
+    fn compute_value(&self, point: &Point) -> Option<u128> {
+        match self.matrix.get(&(point.x, point.y)) {
+            Some(value) => panic!("found value {} in cell expected to be blank", value),
+            None => point.neighbors().iter().try_fold(0u128, |sum, n| {
+                match self.matrix.get(&(n.x, n.y)) {
+                    Some(value) => sum.checked_add(*value),
+                    None => Some(sum),
+                }
+            }),
+        }
+    }
+}
+
+impl Default for Cursor {
+    fn default() -> Cursor {
+        Cursor::new()
+    }
+}
+
+
+// the number, location and value of each square in turn, ending once the values no longer fit in
+// a u128 (after 1561 squares, partway round ring 20) or the numbers go past u64::MAX
+#[derive(Debug)]
+pub struct Values {
+    cursor: Cursor,
+    squares_started: u8, // how many of the first two squares (which the cursor starts on) are done
+}
+
+impl Iterator for Values {
+    type Item = (u64, Point, u128);
+
+    fn next(&mut self) -> Option<(u64, Point, u128)> {
+        // the first two squares are both 1: the center, then the one the cursor starts out on
+        match self.squares_started {
+            0 => {
+                self.squares_started = 1;
+                Some((self.cursor.spiral.origin, Point { x: 0, y: 0 }, 1))
+            }
+            1 => {
+                self.squares_started = 2;
+                Some((self.cursor.index()?, self.cursor.point(), 1))
+            }
+            _ => {
+                let value = self.cursor.next()?;
+                Some((self.cursor.index()?, self.cursor.point(), value))
+            }
+        }
+    }
+}
+
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Direction {
+    Right,
+    Up,
+    Left,
+    Down,
+}
+
+impl Direction {
+    // return the direction one quarter turn from "self", the way the spiral winds
+    fn turn(&self, winding: Winding) -> Direction {
+        match (*self, winding) {
+            (Direction::Right, Winding::CounterClockwise) => Direction::Up,
+            (Direction::Up, Winding::CounterClockwise) => Direction::Left,
+            (Direction::Left, Winding::CounterClockwise) => Direction::Down,
+            (Direction::Down, Winding::CounterClockwise) => Direction::Right,
+            (Direction::Right, Winding::Clockwise) => Direction::Down,
+            (Direction::Down, Winding::Clockwise) => Direction::Left,
+            (Direction::Left, Winding::Clockwise) => Direction::Up,
+            (Direction::Up, Winding::Clockwise) => Direction::Right,
+        }
+    }
+
+    // return how many quarter turns counter-clockwise "self" is from Right
+    fn quarter_turns(&self) -> u32 {
+        match *self {
+            Direction::Right => 0,
+            Direction::Up => 1,
+            Direction::Left => 2,
+            Direction::Down => 3,
+        }
+    }
+
+    // return tuple of changes to x and y needed to shift one space in the "self" diretion
+    fn dxdy(&self) -> (i64, i64) {
+        match *self {
+            Direction::Right => (1, 0),
+            Direction::Up => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Down => (0, -1),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn ring_info_1() {
+        let (ring, point) = Point::get_ring_info(1 - 1);
+        assert_eq!(ring, 0);
+        assert_eq!(point, Point { x: 0, y: 0 });
+    }
+
+    #[test]
+    fn ring_info_5() {
+        let (ring, point) = Point::get_ring_info(5 - 1);
+        assert_eq!(ring, 1);
+        assert_eq!(point, Point { x: 1, y: -1 });
+    }
+
+    #[test]
+    fn ring_info_21() {
+        let (ring, point) = Point::get_ring_info(21 - 1);
+        assert_eq!(ring, 2);
+        assert_eq!(point, Point { x: 2, y: -2 });
+    }
+
+    #[test]
+    fn ring_info_37() {
+        let (ring, point) = Point::get_ring_info(37 - 1);
+        assert_eq!(ring, 3);
+        assert_eq!(point, Point { x: 3, y: -3 });
+    }
+
+    #[test]
+    fn ring_info_2() {
+        let (ring, point) = Point::get_ring_info(2 - 1);
+        assert_eq!(ring, 1);
+        assert_eq!(point, Point { x: 1, y: -1 });
+    }
+
+    #[test]
+    fn ring_info_10() {
+        let (ring, point) = Point::get_ring_info(10 - 1);
+        assert_eq!(ring, 2);
+        assert_eq!(point, Point { x: 2, y: -2 });
+    }
+
+    #[test]
+    fn ring_info_30() {
+        let (ring, point) = Point::get_ring_info(30 - 1);
+        assert_eq!(ring, 3);
+        assert_eq!(point, Point { x: 3, y: -3 });
+    }
+
+    // the spiral walked one square at a time, turning whenever the square to the left is free
+    fn walk(squares: usize) -> Vec<Point> {
+        let mut points = vec![Point { x: 0, y: 0 }];
+        let mut visited = HashSet::new();
+        visited.insert((0, 0));
+        let mut direction = Direction::Down;
+        while points.len() < squares {
+            let point = points[points.len() - 1];
+            let (dx, dy) = direction.turn(Winding::CounterClockwise).dxdy();
+            if !visited.contains(&(point.x + dx, point.y + dy)) {
+                direction = direction.turn(Winding::CounterClockwise);
+            }
+            let (dx, dy) = direction.dxdy();
+            let next = Point { x: point.x + dx, y: point.y + dy };
+            visited.insert((next.x, next.y));
+            points.push(next);
+        }
+        points
+    }
+
+    #[test]
+    fn from_index_matches_walk() {
+        for (i, point) in walk(3_000_000).into_iter().enumerate() {
+            assert_eq!(Point::from_index(i as u64 + 1), Some(point), "index {}", i + 1);
+        }
+    }
+
+    #[test]
+    fn from_index_large() {
+        // the corners of the biggest ring that fits, the bottom right one being (2^32 - 1)^2
+        let k = (1 << 31) - 1;
+        assert_eq!(Point::from_index(0xffff_fffe_0000_0001), Some(Point { x: k, y: -k }));
+        assert_eq!(Point::from_index(0xffff_fffe_0000_0002), Some(Point { x: k + 1, y: -k }));
+        // (u64::MAX is 2^33 - 3 squares further on, near the end of the top edge)
+        assert_eq!(Point::from_index(u64::MAX), Some(Point { x: -k + 1, y: k + 1 }));
+        assert_eq!(Point::from_index(u64::from(u32::MAX)), Some(Point { x: -32766, y: 32768 }));
+        assert_eq!(Point::from_index(0), None);
+    }
+
+    #[test]
+    fn to_index_round_trip() {
+        for index in 1..1_000_000 {
+            assert_eq!(Point::from_index(index).unwrap().to_index(), Some(index));
+        }
+        for x in -200..=200 {
+            for y in -200..=200 {
+                let point = Point { x, y };
+                assert_eq!(Point::from_index(point.to_index().unwrap()), Some(point));
+            }
+        }
+
+        // and indexes scattered all the way up to u64::MAX
+        let mut index: u64 = 1;
+        for _ in 0..100_000 {
+            index = index.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let index = index.max(1);
+            let point = Point::from_index(index).unwrap();
+            assert_eq!(point.to_index(), Some(index), "index {}", index);
+        }
+        assert_eq!(Point::from_index(u64::MAX).unwrap().to_index(), Some(u64::MAX));
+    }
+
+    #[test]
+    fn to_index_out_of_range() {
+        let k = 1 << 31;
+        assert_eq!(Point { x: -k + 2, y: k }.to_index(), Some(u64::MAX));
+        assert_eq!(Point { x: -k + 1, y: k }.to_index(), None);
+        assert_eq!(Point { x: 0, y: i64::MIN }.to_index(), None);
+    }
+
+    const DIRECTIONS: [Direction; 4] =
+        [Direction::Right, Direction::Up, Direction::Left, Direction::Down];
+    const WINDINGS: [Winding; 2] = [Winding::CounterClockwise, Winding::Clockwise];
+
+    #[test]
+    fn spiral_default() {
+        let spiral = Spiral::default();
+        for index in 1..10_000 {
+            let point = Point::from_index(index).unwrap();
+            assert_eq!(spiral.point(index), Some(point));
+            assert_eq!(spiral.index(&point), Some(index));
+        }
+        assert_eq!(spiral.point(0), None);
+    }
+
+    #[test]
+    fn spiral_orientations() {
+        for &winding in WINDINGS.iter() {
+            for &start in DIRECTIONS.iter() {
+                let spiral = Spiral::new(winding, start, 0);
+                let step = |i| {
+                    let (a, b) = (spiral.point(i).unwrap(), spiral.point(i + 1).unwrap());
+                    (b.x - a.x, b.y - a.y)
+                };
+                assert_eq!(step(0), start.dxdy());
+                assert_eq!(step(1), start.turn(winding).dxdy());
+                for index in 0..10_000 {
+                    assert_eq!(spiral.index(&spiral.point(index).unwrap()), Some(index));
+                }
+                assert_eq!(spiral.index(&spiral.point(u64::MAX).unwrap()), Some(u64::MAX));
+            }
+        }
+    }
+
+    #[test]
+    fn spiral_clockwise() {
+        //  6  7  8
+        //  5  0  1
+        //  4  3  2
+        let spiral = Spiral::new(Winding::Clockwise, Direction::Right, 0);
+        let points: Vec<(i64, i64)> = (0..9)
+            .map(|i| spiral.point(i).unwrap())
+            .map(|p| (p.x, p.y))
+            .collect();
+        assert_eq!(
+            points,
+            [(0, 0), (1, 0), (1, -1), (0, -1), (-1, -1), (-1, 0), (-1, 1), (0, 1), (1, 1)]
+        );
+
+        let spiral = Spiral::new(Winding::Clockwise, Direction::Up, 10);
+        assert_eq!(spiral.point(9), None);
+        assert_eq!(spiral.point(12), Some(Point { x: 1, y: 1 }));
+        assert_eq!(spiral.index(&Point { x: 1, y: 1 }), Some(12));
+    }
+
+    #[test]
+    fn cursor_with_spiral() {
+        let mut cursor = Cursor::new();
+        let values: Vec<u128> = (0..50).map(|_| cursor.next().unwrap()).collect();
+        for &winding in WINDINGS.iter() {
+            for &start in DIRECTIONS.iter() {
+                let spiral = Spiral::new(winding, start, 0);
+                let mut cursor = Cursor::with_spiral(spiral);
+                for &value in values.iter() {
+                    assert_eq!(cursor.next(), Some(value));
+                    assert_eq!(spiral.point(cursor.index().unwrap()), Some(cursor.point()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn values() {
+        let spiral = Spiral::default();
+        let first: Vec<u128> = spiral.values().take(10).map(|(_, _, value)| value).collect();
+        assert_eq!(first, [1, 1, 2, 4, 5, 10, 11, 23, 25, 26]);
+
+        // every square is where the index mapping puts it, until the values get too big
+        let mut count = 0;
+        for ((index, point, value), expected_index) in spiral.values().zip(1..) {
+            assert_eq!(index, expected_index);
+            assert_eq!(spiral.point(index), Some(point));
+            assert!(value > 0);
+            count += 1;
+        }
+        assert_eq!(count, 1561);
+
+        // well past where a u32 overflows
+        let big = u128::from(u64::MAX);
+        let (index, point, value) = spiral.values().find(|&(_, _, v)| v > big).unwrap();
+        assert_eq!(Point::from_index(index), Some(point));
+        assert!(value < big * 8);
+    }
+
+    #[test]
+    fn values_max_origin() {
+        let spiral = Spiral::new(Winding::CounterClockwise, Direction::Right, u64::MAX);
+        let values: Vec<(u64, Point, u128)> = spiral.values().collect();
+        assert_eq!(values, [(u64::MAX, Point { x: 0, y: 0 }, 1)]);
+
+        let spiral = Spiral::new(Winding::CounterClockwise, Direction::Right, u64::MAX - 2);
+        assert_eq!(spiral.values().count(), 3);
+        assert_eq!(Cursor::with_spiral(spiral).index(), Some(u64::MAX - 1));
+    }
+
+    #[test]
+    fn part_one_1() {
+        assert_eq!(Day03::part_one(&1).unwrap(), 0);
+    }
+
+    #[test]
+    fn part_one_12() {
+        assert_eq!(Day03::part_one(&12).unwrap(), 3);
+    }
+
+    #[test]
+    fn part_one_23() {
+        assert_eq!(Day03::part_one(&23).unwrap(), 2);
+    }
+
+    #[test]
+    fn part_one_1024() {
+        assert_eq!(Day03::part_one(&1024).unwrap(), 31);
+    }
+
+    #[test]
+    fn value_at_index_1() {
+        let mut cursor = Cursor::new();
+        for _ in 0..5 {
+            cursor.next();
+        }
+        assert_eq!(cursor.matrix.get(&(0, 0)), Some(1).as_ref());
+    }
+
+    #[test]
+    fn value_at_index_2() {
+        let mut cursor = Cursor::new();
+        for _ in 0..5 {
+            cursor.next();
+        }
+        assert_eq!(cursor.matrix.get(&(1, 0)), Some(1).as_ref());
+    }
+
+    #[test]
+    fn value_at_index_4() {
+        let mut cursor = Cursor::new();
+        for _ in 0..5 {
+            cursor.next();
+        }
+        assert_eq!(cursor.matrix.get(&(0, 1)), Some(4).as_ref());
+    }
+
+    #[test]
+    fn value_at_index_5() {
+        let mut cursor = Cursor::new();
+        for _ in 0..5 {
+            cursor.next();
+        }
+        assert_eq!(cursor.matrix.get(&(-1, 1)), Some(5).as_ref());
+    }
+
+    #[test]
+    fn part_two_747() {
+        assert_eq!(Day03::part_two(&362).unwrap(), 747);
+    }
+
+    #[test]
+    fn part_two_overflow() {
+        assert!(Day03::part_two(&u32::MAX).is_err());
+    }
+
+    #[test]
+    fn parse_square() {
+        assert_eq!(Day03::parse("289326\n").unwrap(), 289326);
+        assert_eq!(
+            Day03::parse("  0").unwrap_err().to_string(),
+            "line 1, column 3: square numbers start at 1"
+        );
+        assert_eq!(
+            Day03::parse("12\n13").unwrap_err().to_string(),
+            "line 2, column 1: unexpected \"13\" after the square number"
+        );
+    }
+}
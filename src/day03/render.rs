@@ -0,0 +1,218 @@
+// Pictures of the first few rings of a spiral: a text grid like the reference ones in the comments
+// on Point and Cursor, or an SVG with the path drawn through the squares in order and one square
+// highlighted, e.g. `advent spiral --rings 3 --index 23 --format svg > spiral.svg`.
+
+use super::{Point, Spiral};
+
+
+// what to write in each square
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Label {
+    Index, // the square's number
+    Value, // the value written to it in part two
+}
+
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Square {
+    pub index: u64,
+    pub point: Point,
+    pub label: String,
+}
+
+
+// the squares of the given number of rings around the center, in the order the spiral walks them
+// (values too big for a u128 are left blank, as are squares numbered past u64::MAX)
+pub fn squares(spiral: &Spiral, rings: u32, label: Label) -> Vec<Square> {
+    let side = 2 * u64::from(rings) + 1;
+    let indexes = (0..side * side).map_while(|offset| spiral.origin.checked_add(offset));
+
+    let mut values = spiral.values().map(|(_, _, value)| value);
+
+    indexes
+        .map(|index| Square {
+            index,
+            point: spiral.point(index).unwrap(),
+            label: match label {
+                Label::Index => index.to_string(),
+                Label::Value => values.next().map(|value| value.to_string()).unwrap_or_default(),
+            },
+        })
+        .collect()
+}
+
+
+// the squares laid out as rows of right-aligned labels, with y increasing upwards
+pub fn to_text(squares: &[Square]) -> String {
+    let bounds = Bounds::new(squares);
+    let width = squares.iter().map(|square| square.label.len()).max().unwrap_or(0);
+
+    let mut grid = vec![vec![""; bounds.columns()]; bounds.rows()];
+    for square in squares.iter() {
+        let (column, row) = bounds.cell(&square.point);
+        grid[row][column] = &square.label;
+    }
+
+    let mut text = String::new();
+    for row in grid.iter() {
+        let cells: Vec<String> = row.iter()
+            .map(|label| format!("{:>width$}", label, width = width))
+            .collect();
+        text.push_str(cells.join("  ").trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+
+// the squares as an SVG image, with a line following the spiral through their centers and the
+// target square (if it's one of them) filled in
+pub fn to_svg(squares: &[Square], target: Option<Point>) -> String {
+    let bounds = Bounds::new(squares);
+    let width = squares.iter().map(|square| square.label.len()).max().unwrap_or(0);
+    let size = (9 * width + 16).max(32); // room for the widest label in a 14px monospace font
+    let center = |point: &Point| {
+        let (column, row) = bounds.cell(point);
+        (column * size + size / 2, row * size + size / 2)
+    };
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+         font-family=\"monospace\" font-size=\"14\" text-anchor=\"middle\">\n",
+        bounds.columns() * size,
+        bounds.rows() * size
+    );
+
+    for square in squares.iter() {
+        let (x, y) = center(&square.point);
+        let fill = if Some(square.point) == target { "gold" } else { "white" };
+        svg.push_str(&format!(
+            "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"#999\"/>\n",
+            x - size / 2,
+            y - size / 2,
+            size,
+            size,
+            fill
+        ));
+    }
+
+    let points: Vec<String> = squares.iter()
+        .map(|square| center(&square.point))
+        .map(|(x, y)| format!("{},{}", x, y))
+        .collect();
+    svg.push_str(&format!(
+        "  <polyline points=\"{}\" fill=\"none\" stroke=\"steelblue\" stroke-width=\"2\"/>\n",
+        points.join(" ")
+    ));
+
+    for square in squares.iter().filter(|square| !square.label.is_empty()) {
+        let (x, y) = center(&square.point);
+        svg.push_str(&format!(
+            "  <text x=\"{}\" y=\"{}\" dominant-baseline=\"central\">{}</text>\n",
+            x,
+            y,
+            square.label
+        ));
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+
+// the smallest rectangle of the grid holding every square
+struct Bounds {
+    min_x: i64,
+    max_x: i64,
+    min_y: i64,
+    max_y: i64,
+}
+
+impl Bounds {
+    fn new(squares: &[Square]) -> Bounds {
+        let xs = squares.iter().map(|square| square.point.x);
+        let ys = squares.iter().map(|square| square.point.y);
+        Bounds {
+            min_x: xs.clone().min().unwrap_or(0),
+            max_x: xs.max().unwrap_or(-1),
+            min_y: ys.clone().min().unwrap_or(0),
+            max_y: ys.max().unwrap_or(-1),
+        }
+    }
+
+    fn columns(&self) -> usize {
+        (self.max_x - self.min_x + 1) as usize
+    }
+
+    fn rows(&self) -> usize {
+        (self.max_y - self.min_y + 1) as usize
+    }
+
+    // the (zero-indexed) column and row of a point, counting rows down from the top
+    fn cell(&self, point: &Point) -> (usize, usize) {
+        ((point.x - self.min_x) as usize, (self.max_y - point.y) as usize)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use day03::{Direction, Winding};
+
+    #[test]
+    fn test_to_text() {
+        assert_eq!(
+            to_text(&squares(&Spiral::default(), 3, Label::Index)),
+            "37  36  35  34  33  32  31\n\
+             38  17  16  15  14  13  30\n\
+             39  18   5   4   3  12  29\n\
+             40  19   6   1   2  11  28\n\
+             41  20   7   8   9  10  27\n\
+             42  21  22  23  24  25  26\n\
+             43  44  45  46  47  48  49\n"
+        );
+
+        assert_eq!(
+            to_text(&squares(&Spiral::default(), 2, Label::Value)),
+            "147  142  133  122   59\n\
+             304    5    4    2   57\n\
+             330   10    1    1   54\n\
+             351   11   23   25   26\n\
+             362  747  806  880  931\n"
+        );
+
+        // past the values that fit in a u128, the squares are blank
+        let spiral = Spiral::new(Winding::Clockwise, Direction::Up, 0);
+        let labels: Vec<String> = squares(&spiral, 20, Label::Value)
+            .into_iter()
+            .map(|square| square.label)
+            .collect();
+        assert_eq!(labels.len(), 41 * 41);
+        assert_eq!(labels[..4], ["1", "1", "2", "4"]);
+        assert_ne!(labels[1560], "");
+        assert_eq!(labels[1561], "");
+    }
+
+    #[test]
+    fn test_max_origin() {
+        let spiral = Spiral::new(Winding::CounterClockwise, Direction::Right, u64::MAX);
+        let labels = |label| -> Vec<String> {
+            squares(&spiral, 1, label).into_iter().map(|square| square.label).collect()
+        };
+        assert_eq!(labels(Label::Index), ["18446744073709551615"]);
+        assert_eq!(labels(Label::Value), ["1"]);
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let squares = squares(&Spiral::default(), 1, Label::Index);
+        let svg = to_svg(&squares, Some(Point { x: -1, y: 1 }));
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"96\""));
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"32\" height=\"32\" fill=\"gold\""));
+        assert_eq!(svg.matches("fill=\"gold\"").count(), 1);
+        assert!(svg.contains("points=\"48,48 80,48 80,16 48,16 16,16 16,48 16,80 48,80 80,80\""));
+        assert!(svg.contains("<text x=\"16\" y=\"16\" dominant-baseline=\"central\">5</text>"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
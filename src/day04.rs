@@ -0,0 +1,101 @@
+// http://adventofcode.com/2017/day/4
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use unicode_segmentation::UnicodeSegmentation;
+
+use {Result, Solution};
+
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<String>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    // one passphrase per line
+    fn parse(input: &str) -> Result<Vec<String>> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part_one(passphrases: &Vec<String>) -> Result<usize> {
+        Ok(passphrases.iter().filter(|p| has_no_duplicates(p)).count())
+    }
+
+    fn part_two(passphrases: &Vec<String>) -> Result<usize> {
+        Ok(passphrases.iter().filter(|p| has_no_anagrams(p)).count())
+    }
+}
+
+
+// duplicates of existing words are not allowed
+pub fn has_no_duplicates(line: &str) -> bool {
+    let mut used_words = HashSet::new();
+    let collisions: HashSet<bool> = line.unicode_words().map(|w| used_words.insert(w)).collect();
+    !collisions.contains(&false)
+}
+
+
+// anagrams of existing words are not allowed
+pub fn has_no_anagrams(line: &str) -> bool {
+    let mut used_indexes = BTreeSet::new();
+    let collisions: BTreeSet<bool> = line.unicode_words()
+        .map(|w| used_indexes.insert(create_index_from(w)))
+        .collect();
+    !collisions.contains(&false)
+}
+
+
+fn create_index_from(word: &str) -> BTreeMap<&str, i32> {
+    let mut index = BTreeMap::new();
+    for g in word.graphemes(true) {
+        *(index.entry(g).or_insert(0)) += 1;
+    }
+    index
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicates_1() {
+        assert!(has_no_duplicates("aa bb cc dd ee"));
+    }
+
+    #[test]
+    fn duplicates_2() {
+        assert!(!has_no_duplicates("aa bb cc dd aa"));
+    }
+
+    #[test]
+    fn duplicates_3() {
+        assert!(has_no_duplicates("aa bb cc dd aaa"));
+    }
+
+    #[test]
+    fn anagrams_1() {
+        assert!(has_no_anagrams("abcde fghij"));
+    }
+
+    #[test]
+    fn anagrams_2() {
+        assert!(!has_no_anagrams("abcde xyz ecdab"));
+    }
+
+    #[test]
+    fn anagrams_3() {
+        assert!(has_no_anagrams("a ab abc abd abf abj"));
+    }
+
+    #[test]
+    fn anagrams_4() {
+        assert!(has_no_anagrams("iiii oiii ooii oooi oooo"));
+    }
+
+    #[test]
+    fn anagrams_5() {
+        assert!(!has_no_anagrams("oiii ioii iioi iiio"));
+    }
+}
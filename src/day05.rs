@@ -0,0 +1,174 @@
+// http://adventofcode.com/2017/day/5
+
+use {Error, Result, Solution};
+use error::{abbreviate, parse_token, tokens};
+
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Vec<i32>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    // one jump offset per line
+    fn parse(input: &str) -> Result<Vec<i32>> {
+        let mut instructions = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            let mut found = tokens(line);
+            match found.next() {
+                Some((column, token)) => {
+                    instructions.push(parse_token(token, i + 1, column, "a jump offset")?)
+                }
+                None => return Err(Error::parse(i + 1, 1, "expected a jump offset")),
+            }
+            if let Some((column, token)) = found.next() {
+                let message = format!("expected one jump offset per line, found \"{}\"", abbreviate(token));
+                return Err(Error::parse(i + 1, column, message));
+            }
+        }
+
+        if instructions.is_empty() {
+            return Err(Error::unsolvable("there are no jump offsets to follow"));
+        }
+        Ok(instructions)
+    }
+
+    // number of steps to exit the instruction space
+    fn part_one(instructions: &Vec<i32>) -> Result<u32> {
+        solve(&mut MyCursor::new(instructions, increment))
+    }
+
+    // same as before, but with new rule about modifying read instructions
+    fn part_two(instructions: &Vec<i32>) -> Result<u32> {
+        solve(&mut MyCursor::new(instructions, strange_increment))
+    }
+}
+
+
+fn solve(my_cursor: &mut MyCursor) -> Result<u32> {
+    while !my_cursor.next()? {}
+    Ok(my_cursor.counter)
+}
+
+
+// amount to change an instruction by after it's read (part one: always increment)
+fn increment(_instruction: i64) -> i64 {
+    1
+}
+
+// amount to change an instruction by after it's read (part two)
+fn strange_increment(instruction: i64) -> i64 {
+    match instruction >= 3 {
+        true => -1, // decrement if offset is 3 or more
+        false => 1, // increment otherwise
+    }
+}
+
+
+#[derive(Debug)]
+pub struct MyCursor {
+    index: i64, // index of current instruction
+    counter: u32, // number of instructions consumed so far
+    instructions: Vec<i64>, // instructions to follow (an i64 can't overflow in u32::MAX steps)
+    delta: fn(i64) -> i64, // how much to modify an instruction by after reading it
+}
+
+impl MyCursor {
+    pub fn new(instructions: &[i32], delta: fn(i64) -> i64) -> MyCursor {
+        MyCursor {
+            index: 0,
+            counter: 0,
+            instructions: instructions.iter().map(|&i| i64::from(i)).collect(),
+            delta,
+        }
+    }
+
+    // follow one instruction; returns true once the cursor has escaped the instruction space
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<bool> {
+
+        // increment internal step counter
+        self.counter = self.counter.checked_add(1).ok_or_else(|| {
+            Error::unsolvable(format!("still jumping after {} steps", u32::MAX))
+        })?;
+
+        let instruction = match self.instructions.get_mut(self.index as usize) {
+            Some(i) => {
+                let delta = (self.delta)(*i);
+                *i += delta; // modify the instruction in the vector
+                *i - delta // but pass out its original value to be used
+            }
+            None => panic!("shouldn't get here"),
+        };
+
+        // compute new position using instruction
+        let new_index = self.index + instruction;
+
+        // if new position is out of bounds, we've escaped!
+        if new_index < 0 || self.instructions.len() as i64 <= new_index {
+            return Ok(true);
+        }
+
+        // move to the new position
+        self.index = new_index;
+
+        Ok(false)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_1() {
+        let mut my_cursor = MyCursor::new(&[0, 3, 0, 1, -3], increment);
+
+        assert_eq!(my_cursor.index, 0);
+        assert_eq!(my_cursor.counter, 0);
+        assert_eq!(my_cursor.instructions, vec![0, 3, 0, 1, -3]);
+        assert!(!my_cursor.next().unwrap());
+
+        assert_eq!(my_cursor.index, 0);
+        assert_eq!(my_cursor.counter, 1);
+        assert_eq!(my_cursor.instructions, vec![1, 3, 0, 1, -3]);
+        assert!(!my_cursor.next().unwrap());
+
+        assert_eq!(my_cursor.index, 1);
+        assert_eq!(my_cursor.counter, 2);
+        assert_eq!(my_cursor.instructions, vec![2, 3, 0, 1, -3]);
+        assert!(!my_cursor.next().unwrap());
+
+        assert_eq!(my_cursor.index, 4);
+        assert_eq!(my_cursor.counter, 3);
+        assert_eq!(my_cursor.instructions, vec![2, 4, 0, 1, -3]);
+        assert!(!my_cursor.next().unwrap());
+
+        assert_eq!(my_cursor.index, 1);
+        assert_eq!(my_cursor.counter, 4);
+        assert_eq!(my_cursor.instructions, vec![2, 4, 0, 1, -2]);
+        assert!(my_cursor.next().unwrap());
+
+        assert_eq!(my_cursor.counter, 5);
+    }
+
+    #[test]
+    fn test_2() {
+        assert_eq!(Day05::part_two(&vec![0, 3, 0, 1, -3]).unwrap(), 10);
+    }
+
+    #[test]
+    fn large_offsets() {
+        assert_eq!(Day05::part_one(&vec![1, i32::MAX]).unwrap(), 2);
+        assert_eq!(Day05::part_two(&vec![1, i32::MAX]).unwrap(), 2);
+        assert_eq!(Day05::part_one(&vec![1, i32::MIN]).unwrap(), 2);
+    }
+
+    #[test]
+    fn bad_offset() {
+        let error = Day05::parse("0\n3\n 0.5\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 3, column 2: expected a jump offset, found \"0.5\"");
+    }
+}
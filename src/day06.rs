@@ -0,0 +1,183 @@
+// http://adventofcode.com/2017/day/6
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+use {Error, Result, Solution};
+use error::{parse_token, tokens};
+
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Vec<usize>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    // the number of blocks in each memory bank
+    fn parse(input: &str) -> Result<Vec<usize>> {
+        let mut banks = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            for (column, token) in tokens(line) {
+                banks.push(parse_token(token, i + 1, column, "a number of blocks")?);
+            }
+        }
+
+        if banks.is_empty() {
+            return Err(Error::unsolvable("there are no memory banks to reallocate"));
+        }
+        Ok(banks)
+    }
+
+    // number of iterations before a duplicate state is reached
+    fn part_one(banks: &Vec<usize>) -> Result<usize> {
+        let mut my_cursor = MyCursor::new(banks.clone());
+        my_cursor.run_until_repeat();
+        Ok(my_cursor.steps)
+    }
+
+    // number of iterations between first pair of duplicated states
+    fn part_two(banks: &Vec<usize>) -> Result<usize> {
+        let mut my_cursor = MyCursor::new(banks.clone());
+        my_cursor.run_until_repeat();
+        Ok(my_cursor.loop_size().unwrap())
+    }
+}
+
+
+#[derive(Clone, Debug, PartialEq)]
+struct Bookmark {
+    banks: Vec<usize>,
+    steps: usize,
+}
+
+
+#[derive(Debug)]
+pub struct MyCursor {
+    // representation of memory banks
+    banks: Vec<usize>,
+
+    // number of times step() has been called on this struct
+    steps: usize,
+
+    // hashmap where the keys are all the previously seen states of the memory banks and the values
+    // are the step counts when those states occured
+    seen_states: HashMap<Vec<usize>, usize>,
+
+    // contains None until the *second* occurance of any previously seen state is found, then Some
+    // thereafter (containing a bookmark of the state and the number of steps taken to reach it
+    // since the beginning of the program). Note: finding additional repeated states after the
+    // first will not update this value.
+    first_repeat: Option<Bookmark>,
+}
+
+impl MyCursor {
+    pub fn new(banks: Vec<usize>) -> MyCursor {
+        MyCursor {
+            banks,
+            steps: 0,
+            seen_states: HashMap::new(),
+            first_repeat: None,
+        }
+    }
+
+    pub fn from_string(input: &str) -> Result<MyCursor> {
+        Ok(MyCursor::new(Day06::parse(input)?))
+    }
+
+    // step until the first repeated state has been found
+    pub fn run_until_repeat(&mut self) {
+        while self.first_repeat.is_none() {
+            self.step();
+        }
+    }
+
+    pub fn step(&mut self) {
+        // update the step counter
+        self.steps += 1;
+
+        // find the index and value of the bank containing the most blocks
+        let (max_index, max_value) = self.banks
+            .iter()
+            .enumerate()
+            .fold(None, |max, curr_tuple| {
+                let curr_i = curr_tuple.0;
+                let curr_v = *curr_tuple.1;
+                match max {
+                    None => Some((curr_i, curr_v)),
+                    Some((prev_i, prev_v)) => {
+                        if curr_v > prev_v {
+                            Some((curr_i, curr_v))
+                        } else {
+                            Some((prev_i, prev_v))
+                        }
+                    }
+                }
+            })
+            .unwrap();
+
+        // overwrite the bank at max_index to contain 0 blocks
+        *(self.banks.get_mut(max_index).unwrap()) = 0;
+
+        // distribute "max_value" blocks across all memory banks, starting with the bank
+        // immediately after the one at max_index
+        let length = self.banks.len();
+        for i in 0..max_value {
+            *(self.banks.get_mut((max_index + i + 1) % length).unwrap()) += 1;
+        }
+
+        // try to insert the current (just updated) state to the map of seen states
+        let insert_succeeded = match self.seen_states.entry(self.banks.clone()) {
+            Entry::Occupied(..) => false,
+            Entry::Vacant(e) => {
+                e.insert(self.steps);
+                true
+            }
+        };
+
+        // if the insertion failed (because the current state's already been seen before) and no
+        // first repeated state bookmark exists yet
+        if !insert_succeeded && self.first_repeat.is_none() {
+            // bookmark the current state as the first repeated state
+            self.first_repeat = Some(Bookmark {
+                banks: self.banks.clone(),
+                steps: self.steps,
+            });
+        }
+    }
+
+    pub fn loop_size(&self) -> Option<usize> {
+        self.first_repeat
+            .as_ref()
+            .map(|fr| fr.steps - self.seen_states.get(&fr.banks).unwrap())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_1() {
+        assert_eq!(Day06::part_one(&Day06::parse("0 2 7 0").unwrap()).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_2() {
+        assert_eq!(Day06::part_two(&Day06::parse("0 2 7 0").unwrap()).unwrap(), 4);
+    }
+
+    #[test]
+    fn from_string() {
+        let mut my_cursor = MyCursor::from_string("0 2 7 0").unwrap();
+        my_cursor.step();
+        assert_eq!(my_cursor.banks, vec![2, 4, 1, 2]);
+    }
+
+    #[test]
+    fn bad_bank() {
+        let error = Day06::parse("0 2 -7 0").unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 5: expected a number of blocks, found \"-7\"");
+    }
+}
@@ -0,0 +1,95 @@
+// Graphviz DOT export of a tower, e.g. `advent tower dot | dot -Tsvg > tower.svg`.
+//
+// Each node is labeled with the program's name, its own weight and the total weight it's holding
+// up. The root is filled in, programs whose children don't balance are outlined in red, and the
+// program whose weight needs correcting (if there is one) is filled in red.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use Result;
+use super::Tower;
+use super::report::report;
+
+
+pub fn to_dot<'a>(tower: &Tower<'a>, root: &'a str) -> Result<String> {
+    let rows = report(tower, root)?;
+    let unbalanced: HashSet<&str> = rows.iter()
+        .filter(|row| !row.balanced)
+        .map(|row| row.program)
+        .collect();
+
+    // a tower that balances (or can't be balanced) just has nothing to correct
+    let correction = tower.find_unbalanced(root).ok();
+
+    // (writing to a String can't fail, so the results of write! are ignored)
+    let mut dot = String::new();
+    dot.push_str("digraph tower {\n");
+    dot.push_str("    node [shape=box];\n");
+
+    for row in rows.iter() {
+        let mut label = format!("{}\\n{} ({})", escape(row.program), row.weight, row.total_weight);
+        let mut attributes = Vec::new();
+
+        if row.program == root {
+            attributes.push("style=filled, fillcolor=gold");
+        }
+        if unbalanced.contains(row.program) {
+            attributes.push("color=red, penwidth=2");
+        }
+        if let Some(ref correction) = correction {
+            if correction.program == row.program {
+                let _ = write!(label, "\\nshould weigh {}", correction.corrected_weight);
+                attributes.push("style=filled, fillcolor=salmon");
+            }
+        }
+
+        let _ = write!(dot, "    \"{}\" [label=\"{}\"", escape(row.program), label);
+        for attribute in attributes.iter() {
+            let _ = write!(dot, ", {}", attribute);
+        }
+        dot.push_str("];\n");
+    }
+
+    for row in rows.iter() {
+        if let Some(parent) = row.parent {
+            let _ = writeln!(dot, "    \"{}\" -> \"{}\";", escape(parent), escape(row.program));
+        }
+    }
+
+    dot.push_str("}\n");
+    Ok(dot)
+}
+
+
+// escape a string for use inside a double-quoted DOT ID
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Solution;
+    use day07::Day07;
+
+    #[test]
+    fn test_to_dot() {
+        let programs = Day07::parse("aa (1) -> bb, cc, dd\nbb (2)\ncc (3)\ndd (2)\n").unwrap();
+        let dot = to_dot(&Tower::new(&programs), "aa").unwrap();
+        assert_eq!(
+            dot,
+            "digraph tower {\n    \
+                 node [shape=box];\n    \
+                 \"aa\" [label=\"aa\\n1 (8)\", style=filled, fillcolor=gold, color=red, penwidth=2];\n    \
+                 \"bb\" [label=\"bb\\n2 (2)\"];\n    \
+                 \"cc\" [label=\"cc\\n3 (3)\\nshould weigh 2\", style=filled, fillcolor=salmon];\n    \
+                 \"dd\" [label=\"dd\\n2 (2)\"];\n    \
+                 \"aa\" -> \"bb\";\n    \
+                 \"aa\" -> \"cc\";\n    \
+                 \"aa\" -> \"dd\";\n\
+             }\n"
+        );
+    }
+}
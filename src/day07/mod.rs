@@ -0,0 +1,347 @@
+// http://adventofcode.com/2017/day/7
+
+use petgraph::Direction;
+use petgraph::graphmap::DiGraphMap;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+
+use {Error, Result, Solution};
+
+pub mod dot;
+mod parser;
+pub mod query;
+pub mod report;
+pub mod validate;
+
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<Program>;
+    type PartOne = String;
+    type PartTwo = i32;
+
+    fn parse(input: &str) -> Result<Vec<Program>> {
+        input
+            .lines()
+            .enumerate()
+            .filter(|&(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| parser::parse_line(i + 1, line))
+            .collect()
+    }
+
+    // find root of tree
+    fn part_one(programs: &Vec<Program>) -> Result<String> {
+        let tower = Tower::validated(programs)?;
+        Ok(get_root(&tower.tree)?.to_string())
+    }
+
+    // find the one program with the wrong weight, and what its weight should be
+    fn part_two(programs: &Vec<Program>) -> Result<i32> {
+        let tower = Tower::validated(programs)?;
+        let root = get_root(&tower.tree)?;
+        Ok(tower.find_unbalanced(root)?.corrected_weight)
+    }
+}
+
+
+// one line of puzzle input: a program, its weight, and the programs it's holding up
+#[derive(Clone, Debug, PartialEq)]
+pub struct Program {
+    pub line: usize, // where in the input the program was described (one-indexed)
+    pub name: String,
+    pub weight: i32,
+    pub children: Vec<String>,
+}
+
+
+// the programs arranged as a tree, with an edge from each program to each one it's holding up
+pub struct Tower<'a> {
+    pub tree: DiGraphMap<&'a str, ()>,
+    pub weights: HashMap<&'a str, i32>,
+}
+
+impl<'a> Tower<'a> {
+    pub fn new(programs: &'a [Program]) -> Tower<'a> {
+        // initialize tree and weight map
+        let mut tree: DiGraphMap<&str, ()> = DiGraphMap::new();
+        let mut weights: HashMap<&str, i32> = HashMap::new();
+
+        // populate tree and weight map using puzzle input
+        for program in programs.iter() {
+            let name = program.name.as_str();
+
+            // insert programs's own data into tree and weight map
+            tree.add_node(name);
+            weights.insert(name, program.weight);
+
+            // insert programs's children's data into structures (if applicable)
+            for child in program.children.iter() {
+                tree.add_edge(name, child.as_str(), ());
+            }
+        }
+
+        Tower { tree, weights }
+    }
+
+    // like new, but first checks the programs form a single tower, reporting every problem found
+    pub fn validated(programs: &'a [Program]) -> Result<Tower<'a>> {
+        let problems = validate::validate(programs);
+        if !problems.is_empty() {
+            return Err(Error::Invalid(problems.iter().map(ToString::to_string).collect()));
+        }
+        Ok(Tower::new(programs))
+    }
+
+    // the programs the given program is holding up
+    pub fn children(&self, node: &'a str) -> Vec<&'a str> {
+        self.tree.neighbors_directed(node, Direction::Outgoing).collect()
+    }
+
+    // the weight of a program by itself
+    pub fn weight(&self, node: &str) -> Result<i32> {
+        match self.weights.get(node) {
+            Some(weight) => Ok(*weight),
+            None => Err(Error::unsolvable(format!("\"{}\" has no weight", node))),
+        }
+    }
+
+    // the total weight of every program in the tower above (and including) the given one, keyed
+    // by program (as i64s, since a few programs' 32-bit weights can add up to more than fits)
+    pub fn total_weights(&self, root: &'a str) -> Result<HashMap<&'a str, i64>> {
+        let mut totals = HashMap::new();
+        let mut started = HashSet::new();
+
+        // a program's total can be worked out once its children's are, so each program goes on
+        // the stack twice: once to push its children after it, and again once they're all done
+        // (this is a loop rather than recursion so that very tall towers can't overflow the stack)
+        let mut stack = vec![(root, false)];
+        while let Some((node, children_done)) = stack.pop() {
+            let children = self.children(node);
+            if children_done {
+                let mut total = i64::from(self.weight(node)?);
+                for child in children {
+                    total += totals[child];
+                }
+                totals.insert(node, total);
+                continue;
+            }
+
+            if !started.insert(node) {
+                let message = format!("\"{}\" is held up more than once, or in a loop", node);
+                return Err(Error::unsolvable(message));
+            }
+            stack.push((node, true));
+            stack.extend(children.into_iter().map(|child| (child, false)));
+        }
+
+        Ok(totals)
+    }
+
+    // find the single program whose weight keeps the tower from balancing
+    pub fn find_unbalanced(&self, root: &'a str) -> Result<Correction<'a>> {
+        let totals = self.total_weights(root)?;
+
+        // follow the odd one out up the tower for as long as there is one; the last program on
+        // that path has balanced children, so its own weight must be what's wrong
+        let mut node = root;
+        let mut expected_total = None;
+        while let Some((child, expected)) = odd_one_out(node, &self.children(node), &totals)? {
+            node = child;
+            expected_total = Some(expected);
+        }
+
+        match expected_total {
+            Some(expected) => {
+                let weight = self.weight(node)?;
+                let corrected_weight = i64::from(weight) + expected - totals[node];
+                Ok(Correction {
+                    program: node,
+                    weight,
+                    corrected_weight: i32::try_from(corrected_weight).map_err(|_| {
+                        Error::unsolvable(format!(
+                            "\"{}\" would have to weigh {}, which doesn't fit in 32 bits",
+                            node, corrected_weight
+                        ))
+                    })?,
+                })
+            }
+            None => Err(Error::unsolvable("the tower is already balanced")),
+        }
+    }
+}
+
+
+// a program whose weight needs to change for the tower to balance
+#[derive(Debug, PartialEq)]
+pub struct Correction<'a> {
+    pub program: &'a str,
+    pub weight: i32,
+    pub corrected_weight: i32,
+}
+
+
+// if exactly one of the children has a different total weight from the rest, return it and the
+// total weight it should have (or None if all the children weigh the same)
+fn odd_one_out<'a>(
+    parent: &str,
+    children: &[&'a str],
+    totals: &HashMap<&'a str, i64>,
+) -> Result<Option<(&'a str, i64)>> {
+    // group the children by their total weight
+    let mut by_total: HashMap<i64, Vec<&'a str>> = HashMap::new();
+    for child in children.iter() {
+        by_total.entry(totals[child]).or_default().push(child);
+    }
+
+    if by_total.len() <= 1 {
+        return Ok(None);
+    }
+
+    let odd: Vec<(&i64, &Vec<&str>)> = by_total.iter().filter(|&(_, c)| c.len() == 1).collect();
+    let even: Vec<(&i64, &Vec<&str>)> = by_total.iter().filter(|&(_, c)| c.len() > 1).collect();
+    match (odd.as_slice(), even.as_slice()) {
+        (&[(_, odd)], &[(&expected, _)]) => Ok(Some((odd[0], expected))),
+        _ => Err(Error::unsolvable(format!(
+            "can't tell which program above \"{}\" has the wrong weight",
+            parent
+        ))),
+    }
+}
+
+
+// follow parents down from any program until reaching one with no parent
+pub fn get_root<'a>(tree: &DiGraphMap<&'a str, ()>) -> Result<&'a str> {
+    let mut node = match tree.nodes().next() {
+        Some(node) => node,
+        None => return Err(Error::unsolvable("there are no programs in the tower")),
+    };
+
+    // a path down longer than the number of programs must have gone around a loop
+    for _ in 0..tree.node_count() {
+        let parents: Vec<&str> = tree.neighbors_directed(node, Direction::Incoming).collect();
+        match parents.as_slice() {
+            [] => return Ok(node),
+            [parent] => node = *parent,
+            _ => {
+                return Err(Error::unsolvable(format!(
+                    "\"{}\" has multiple parents: {:?}",
+                    node, parents
+                )))
+            }
+        }
+    }
+    Err(Error::unsolvable(format!("\"{}\" is part of a loop with no root", node)))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_root() {
+        let input = "pbga (66)\nxhth (57)\nebii (61)\nhavc (66)\nktlj (57)\n\
+                     fwft (72) -> ktlj, cntj, xhth\nqoyq (66)\npadx (45) -> pbga, havc, qoyq\n\
+                     tknk (41) -> ugml, padx, fwft\njptl (61)\nugml (68) -> gyxo, ebii, jptl\n\
+                     gyxo (61)\ncntj (57)\n";
+        assert_eq!(Day07::part_one(&Day07::parse(input).unwrap()).unwrap(), "tknk");
+    }
+
+    #[test]
+    fn test_unbalanced() {
+        let programs = Day07::parse(include_str!("../../data/day07-test.txt")).unwrap();
+        let tower = Tower::new(&programs);
+        let correction = tower.find_unbalanced("tknk").unwrap();
+        assert_eq!(correction, Correction { program: "ugml", weight: 68, corrected_weight: 60 });
+    }
+
+    #[test]
+    fn test_total_weights() {
+        let programs = Day07::parse(include_str!("../../data/day07-test.txt")).unwrap();
+        let totals = Tower::new(&programs).total_weights("tknk").unwrap();
+        assert_eq!(totals["ugml"], 251);
+        assert_eq!(totals["padx"], 243);
+        assert_eq!(totals["fwft"], 243);
+        assert_eq!(totals["tknk"], 41 + 251 + 243 + 243);
+    }
+
+    #[test]
+    fn test_balanced() {
+        let programs = Day07::parse("aa (1) -> bb, cc\nbb (2)\ncc (2)\n").unwrap();
+        assert!(Day07::part_two(&programs).is_err());
+    }
+
+    #[test]
+    fn test_bad_line() {
+        let error = Day07::parse("pbga (66)\nxhth (57) <- ebii\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 11: expected \"->\" or end of line, found \"<-\""
+        );
+    }
+
+    #[test]
+    fn test_multiple_parents() {
+        let programs = Day07::parse("cc (1)\naa (1) -> cc\nbb (1) -> cc\n").unwrap();
+        assert!(Day07::part_one(&programs).is_err());
+    }
+
+    #[test]
+    fn test_invalid() {
+        let programs = Day07::parse("aa (1) -> bb\nbb (1) -> aa, cc\n").unwrap();
+        assert_eq!(
+            Day07::part_one(&programs).unwrap_err().to_string(),
+            "found 2 problems in the input:\n  \
+             line 2: \"cc\" is never given a weight\n  \
+             lines 1, 2: \"aa\", \"bb\" hold each other up in a loop"
+        );
+    }
+
+    #[test]
+    fn test_heavy_weights() {
+        let programs = Day07::parse("aa (2147483647) -> bb, cc\nbb (1)\ncc (1)\n").unwrap();
+        assert_eq!(Day07::part_one(&programs).unwrap(), "aa");
+        assert_eq!(
+            Day07::part_two(&programs).unwrap_err().to_string(),
+            "the tower is already balanced"
+        );
+
+        let input = "aa (1) -> bb, cc, dd\nbb (2147483647)\ncc (2147483647)\ndd (5)\n";
+        assert_eq!(Day07::part_two(&Day07::parse(input).unwrap()).unwrap(), i32::MAX);
+
+        // the fix would have to weigh more than an i32 holds
+        let input = "aa (1) -> bb, cc, dd\n\
+                     bb (2147483647) -> x\nx (2147483647)\n\
+                     cc (2147483647) -> y\ny (2147483647)\n\
+                     dd (1) -> z\nz (1)\n";
+        assert_eq!(
+            Day07::part_two(&Day07::parse(input).unwrap()).unwrap_err().to_string(),
+            "\"dd\" would have to weigh 4294967293, which doesn't fit in 32 bits"
+        );
+    }
+
+    #[test]
+    fn test_tall_tower() {
+        let levels = 5000;
+        let mut input: String = (0..levels - 1)
+            .map(|i| format!("p{} (1) -> p{}\n", i, i + 1))
+            .collect();
+        input.push_str(&format!("p{} (1)\n", levels - 1));
+        let programs = Day07::parse(&input).unwrap();
+
+        assert_eq!(Day07::part_one(&programs).unwrap(), "p0");
+        let tower = Tower::validated(&programs).unwrap();
+        assert_eq!(tower.total_weights("p0").unwrap()["p0"], levels);
+        assert_eq!(
+            Day07::part_two(&programs).unwrap_err().to_string(),
+            "the tower is already balanced"
+        );
+    }
+
+    #[test]
+    fn test_get_root_loop() {
+        let programs = Day07::parse("aa (1) -> bb\nbb (1) -> aa\n").unwrap();
+        assert!(get_root(&Tower::new(&programs).tree).is_err());
+    }
+}
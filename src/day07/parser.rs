@@ -0,0 +1,187 @@
+// Grammar for one line of day 7 input:
+//
+//     line     = name weight [ "->" name { "," name } ]
+//     name     = one or more letters, digits or underscores
+//     weight   = "(" digits ")"
+//
+// with spaces or tabs allowed between any two pieces. Each piece says what it expected to find if
+// it's missing, so a bad line can be reported as e.g. "expected ')' after weight, found "]"".
+
+use nom::bytes::complete::{tag, take_while1};
+use nom::character::complete::{char, digit1, space0};
+use nom::combinator::{cut, eof, opt};
+use nom::error::{ErrorKind, ParseError};
+use nom::multi::many0;
+use nom::sequence::preceded;
+use nom::{Err, IResult};
+
+use error::{abbreviate, column_of};
+use {Error, Result};
+use super::Program;
+
+
+// where a line stopped matching the grammar, and what should have been there instead
+#[derive(Debug, PartialEq)]
+pub struct Expected<'a> {
+    pub rest: &'a str,
+    pub what: &'static str,
+}
+
+impl<'a> ParseError<&'a str> for Expected<'a> {
+    fn from_error_kind(rest: &'a str, _: ErrorKind) -> Expected<'a> {
+        Expected {
+            rest,
+            what: "something else",
+        }
+    }
+
+    // the innermost failure says the most about what went wrong
+    fn append(_: &'a str, _: ErrorKind, other: Expected<'a>) -> Expected<'a> {
+        other
+    }
+}
+
+type Parsed<'a, T> = IResult<&'a str, T, Expected<'a>>;
+
+
+// parse a line of input into its significant values
+pub fn parse_line(line_number: usize, line: &str) -> Result<Program> {
+    match line_parser(line) {
+        Ok((_, (name, weight, children))) => Ok(Program {
+            line: line_number,
+            name: name.to_string(),
+            weight,
+            children: children.into_iter().map(String::from).collect(),
+        }),
+        Err(Err::Error(e)) | Err(Err::Failure(e)) => {
+            let found = match e.rest.split_whitespace().next() {
+                Some(token) => format!("\"{}\"", abbreviate(token)),
+                None => String::from("end of line"),
+            };
+            let message = format!("expected {}, found {}", e.what, found);
+            Err(Error::parse(line_number, column_of(line, e.rest), message))
+        }
+        // complete parsers never ask for more input
+        Err(Err::Incomplete(_)) => Err(Error::parse(line_number, 1, "line ended unexpectedly")),
+    }
+}
+
+
+// describe what a parser was looking for whenever it fails
+fn expect<'a, T, P>(what: &'static str, mut parser: P) -> impl FnMut(&'a str) -> Parsed<'a, T>
+where
+    P: FnMut(&'a str) -> Parsed<'a, T>,
+{
+    move |input| parser(input).map_err(|e| e.map(|e| Expected { rest: e.rest, what }))
+}
+
+
+fn program_parser<'a>(input: &'a str) -> Parsed<'a, &'a str> {
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    expect("a program name", take_while1(is_name_char))(input)
+}
+
+fn integer_parser<'a>(input: &'a str) -> Parsed<'a, i32> {
+    let (rest, digits) = expect("a weight", digit1)(input)?;
+    match digits.parse() {
+        Ok(weight) => Ok((rest, weight)),
+        Err(_) => Err(Err::Failure(Expected {
+            rest: input,
+            what: "a weight that fits in 32 bits",
+        })),
+    }
+}
+
+fn weight_parser<'a>(input: &'a str) -> Parsed<'a, i32> {
+    let (input, _) = expect("'(' before weight", char('('))(input)?;
+    let (input, weight) = cut(preceded(space0, integer_parser))(input)?;
+    let (input, _) = cut(preceded(space0, expect("')' after weight", char(')'))))(input)?;
+    Ok((input, weight))
+}
+
+// "-> name, name, ..." (once there's an arrow, there has to be at least one name after it)
+fn children_parser<'a>(input: &'a str) -> Parsed<'a, Vec<&'a str>> {
+    let (input, _) = tag("->")(input)?;
+    let (input, first) = cut(preceded(space0, program_parser))(input)?;
+    let (input, mut rest) = many0(preceded(
+        preceded(space0, char(',')),
+        cut(preceded(space0, program_parser)),
+    ))(input)?;
+    rest.insert(0, first);
+    Ok((input, rest))
+}
+
+fn line_parser<'a>(input: &'a str) -> Parsed<'a, (&'a str, i32, Vec<&'a str>)> {
+    let (input, name) = preceded(space0, program_parser)(input)?;
+    let (input, weight) = preceded(space0, weight_parser)(input)?;
+    let (input, children) = preceded(space0, opt(children_parser))(input)?;
+
+    let what = match children {
+        Some(_) => "',' or end of line",
+        None => "\"->\" or end of line",
+    };
+    let (input, _) = preceded(space0, expect(what, eof))(input)?;
+    Ok((input, (name, weight, children.unwrap_or_default())))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_program_parser() {
+        assert_eq!(program_parser("aaa"), Ok(("", "aaa")));
+        assert_eq!(program_parser("a_1 (2)"), Ok((" (2)", "a_1")));
+    }
+
+    #[test]
+    fn test_integer_parser() {
+        assert_eq!(integer_parser("100"), Ok(("", 100)));
+    }
+
+    #[test]
+    fn test_weight_parser() {
+        assert_eq!(weight_parser("( 100 )"), Ok(("", 100)));
+    }
+
+    #[test]
+    fn test_children_parser() {
+        assert_eq!(children_parser("-> aa,bb , cc"), Ok(("", vec!["aa", "bb", "cc"])));
+    }
+
+    #[test]
+    fn test_line_parser_without_children() {
+        assert_eq!(line_parser("pbga (66)"), Ok(("", ("pbga", 66, vec![]))));
+    }
+
+    #[test]
+    fn test_line_parser_with_children() {
+        let line = "aaa (100) -> bbb, ccc, ddd";
+        let (_, (program, weight, children)) = line_parser(line).unwrap();
+        assert_eq!(program, "aaa");
+        assert_eq!(weight, 100);
+        assert_eq!(children, vec!["bbb", "ccc", "ddd"]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let cases = [
+            ("(66)", 1, "a program name, found \"(66)\""),
+            ("aa 66", 4, "'(' before weight, found \"66\""),
+            ("aa (x)", 5, "a weight, found \"x)\""),
+            ("aa (9999999999)", 5, "a weight that fits in 32 bits, found \"9999999999)\""),
+            ("aa (66]", 7, "')' after weight, found \"]\""),
+            ("aa (66", 7, "')' after weight, found end of line"),
+            ("aa (66) ->", 11, "a program name, found end of line"),
+            ("aa (66) -> bb,", 15, "a program name, found end of line"),
+            ("aa (66) -> bb cc", 15, "',' or end of line, found \"cc\""),
+        ];
+        for &(line, column, expected) in cases.iter() {
+            assert_eq!(
+                parse_line(3, line).unwrap_err().to_string(),
+                format!("line 3, column {}: expected {}", column, expected)
+            );
+        }
+    }
+}
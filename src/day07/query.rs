@@ -0,0 +1,143 @@
+// Questions about where a program sits in a tower, for poking around an input without writing
+// throwaway code (see `advent tower shell`).
+
+use petgraph::Direction;
+use std::collections::HashMap;
+
+use {Error, Result};
+use super::{get_root, Program, Tower};
+
+
+pub struct Explorer<'a> {
+    tower: Tower<'a>,
+    root: &'a str,
+    totals: HashMap<&'a str, i64>,
+}
+
+impl<'a> Explorer<'a> {
+    // an explorer over the tower the programs make up, once they're checked to form exactly one
+    // (so every question about it has an answer)
+    pub fn new(programs: &'a [Program]) -> Result<Explorer<'a>> {
+        let tower = Tower::validated(programs)?;
+        let root = get_root(&tower.tree)?;
+        let totals = tower.total_weights(root)?;
+        Ok(Explorer {
+            tower,
+            root,
+            totals,
+        })
+    }
+
+    pub fn root(&self) -> &'a str {
+        self.root
+    }
+
+    // a program's own weight and the total weight of everything it's holding up (including itself)
+    pub fn weights(&self, name: &str) -> Result<(i32, i64)> {
+        let program = self.lookup(name)?;
+        Ok((self.tower.weight(program)?, self.totals[program]))
+    }
+
+    // the program holding this one up (None for the root)
+    pub fn parent(&self, name: &str) -> Result<Option<&'a str>> {
+        let program = self.lookup(name)?;
+        Ok(self.tower.tree.neighbors_directed(program, Direction::Incoming).next())
+    }
+
+    // every program from the root up to (and including) this one
+    pub fn path(&self, name: &str) -> Result<Vec<&'a str>> {
+        let mut path = vec![self.lookup(name)?];
+        while let Some(parent) = self.parent(path[path.len() - 1])? {
+            path.push(parent);
+        }
+        path.reverse();
+        Ok(path)
+    }
+
+    // the other programs held up by this one's parent, sorted by name
+    pub fn siblings(&self, name: &str) -> Result<Vec<&'a str>> {
+        let program = self.lookup(name)?;
+        let mut siblings = match self.parent(program)? {
+            Some(parent) => self.tower.children(parent),
+            None => Vec::new(),
+        };
+        siblings.retain(|&sibling| sibling != program);
+        siblings.sort();
+        Ok(siblings)
+    }
+
+    // of the programs this one is holding up, the one with the most total weight (and that total),
+    // or None for a leaf; ties go to the first name alphabetically
+    pub fn heaviest(&self, name: &str) -> Result<Option<(&'a str, i64)>> {
+        let mut children = self.tower.children(self.lookup(name)?);
+        children.sort();
+        Ok(children
+            .into_iter()
+            .map(|child| (child, self.totals[child]))
+            .fold(None, |heaviest, (child, total)| match heaviest {
+                Some((_, most)) if most >= total => heaviest,
+                _ => Some((child, total)),
+            }))
+    }
+
+    // the programs at the top of the tower above this one (itself, if it's holding nothing up),
+    // sorted by name
+    pub fn leaves(&self, name: &str) -> Result<Vec<&'a str>> {
+        let mut leaves = Vec::new();
+        let mut stack = vec![self.lookup(name)?];
+        while let Some(program) = stack.pop() {
+            let children = self.tower.children(program);
+            if children.is_empty() {
+                leaves.push(program);
+            }
+            stack.extend(children);
+        }
+        leaves.sort();
+        Ok(leaves)
+    }
+
+    // the tower's own copy of a program's name
+    fn lookup(&self, name: &str) -> Result<&'a str> {
+        match self.tower.weights.get_key_value(name) {
+            Some((&program, _)) => Ok(program),
+            None => Err(Error::unsolvable(format!("there is no program called \"{}\"", name))),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Solution;
+    use day07::Day07;
+
+    #[test]
+    fn test_explorer() {
+        let programs = Day07::parse(include_str!("../../data/day07-test.txt")).unwrap();
+        let explorer = Explorer::new(&programs).unwrap();
+
+        assert_eq!(explorer.root(), "tknk");
+        assert_eq!(explorer.weights("ugml").unwrap(), (68, 251));
+        assert_eq!(explorer.parent("ugml").unwrap(), Some("tknk"));
+        assert_eq!(explorer.parent("tknk").unwrap(), None);
+        assert_eq!(explorer.path("ebii").unwrap(), vec!["tknk", "ugml", "ebii"]);
+        assert_eq!(explorer.siblings("padx").unwrap(), vec!["fwft", "ugml"]);
+        assert_eq!(explorer.siblings("tknk").unwrap(), Vec::<&str>::new());
+        assert_eq!(explorer.heaviest("tknk").unwrap(), Some(("ugml", 251)));
+        assert_eq!(explorer.heaviest("padx").unwrap(), Some(("havc", 66)));
+        assert_eq!(explorer.heaviest("pbga").unwrap(), None);
+        assert_eq!(explorer.leaves("padx").unwrap(), vec!["havc", "pbga", "qoyq"]);
+        assert_eq!(explorer.leaves("pbga").unwrap(), vec!["pbga"]);
+        assert_eq!(
+            explorer.parent("nope").unwrap_err().to_string(),
+            "there is no program called \"nope\""
+        );
+    }
+
+    #[test]
+    fn test_explorer_invalid() {
+        let programs = Day07::parse("aa (1)\nbb (1) -> cc\ncc (1) -> bb\n").unwrap();
+        assert!(Explorer::new(&programs).is_err());
+    }
+}
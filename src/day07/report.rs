@@ -0,0 +1,152 @@
+// Per-program summary of a tower: how much each program weighs, how much it's holding up, and
+// whether the programs it's holding up balance.
+
+use std::io::{self, Write};
+
+use Result;
+use super::Tower;
+
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReportRow<'a> {
+    pub program: &'a str,
+    pub parent: Option<&'a str>,
+    pub depth: usize,
+    pub weight: i32,
+    pub total_weight: i64,
+    pub children: usize,
+    pub balanced: bool, // true if every child has the same total weight (always true for leaves)
+}
+
+
+// one row per program, in depth-first order from the root with siblings sorted by name (so
+// reports of different inputs can be diffed)
+pub fn report<'a>(tower: &Tower<'a>, root: &'a str) -> Result<Vec<ReportRow<'a>>> {
+    let totals = tower.total_weights(root)?;
+    let mut rows = Vec::new();
+
+    // (program, parent, depth) of programs still to visit, sorted so the next one pops off the end
+    let mut stack = vec![(root, None, 0)];
+    while let Some((program, parent, depth)) = stack.pop() {
+        let mut children = tower.children(program);
+        children.sort();
+
+        rows.push(ReportRow {
+            program,
+            parent,
+            depth,
+            weight: tower.weight(program)?,
+            total_weight: totals[program],
+            children: children.len(),
+            balanced: children.windows(2).all(|pair| totals[pair[0]] == totals[pair[1]]),
+        });
+
+        stack.extend(children.iter().rev().map(|&child| (child, Some(program), depth + 1)));
+    }
+
+    Ok(rows)
+}
+
+
+// write the report as an indented tree, one program per line
+pub fn write_tree<W: Write>(rows: &[ReportRow], out: &mut W) -> io::Result<()> {
+    for row in rows.iter() {
+        write!(
+            out,
+            "{:indent$}{} ({}) total {}",
+            "",
+            row.program,
+            row.weight,
+            row.total_weight,
+            indent = row.depth * 2
+        )?;
+        if row.children > 0 {
+            let balance = if row.balanced { "balanced" } else { "UNBALANCED" };
+            write!(out, ", {} children {}", row.children, balance)?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+
+// write the report as CSV with a header row
+pub fn write_csv<W: Write>(rows: &[ReportRow], out: &mut W) -> io::Result<()> {
+    writeln!(out, "program,parent,depth,weight,total_weight,children,balanced")?;
+    for row in rows.iter() {
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{}",
+            row.program,
+            row.parent.unwrap_or(""),
+            row.depth,
+            row.weight,
+            row.total_weight,
+            row.children,
+            row.balanced
+        )?;
+    }
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Solution;
+    use day07::{Day07, Program};
+
+    fn programs() -> Vec<Program> {
+        Day07::parse("aa (1) -> cc, bb\nbb (2) -> dd, ee\ncc (3)\ndd (4)\nee (5)\n").unwrap()
+    }
+
+    #[test]
+    fn test_report() {
+        let programs = programs();
+        let rows = report(&Tower::new(&programs), "aa").unwrap();
+        let order: Vec<&str> = rows.iter().map(|row| row.program).collect();
+        assert_eq!(order, vec!["aa", "bb", "dd", "ee", "cc"]);
+        assert_eq!(
+            rows[1],
+            ReportRow {
+                program: "bb",
+                parent: Some("aa"),
+                depth: 1,
+                weight: 2,
+                total_weight: 11,
+                children: 2,
+                balanced: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_write_tree() {
+        let programs = programs();
+        let rows = report(&Tower::new(&programs), "aa").unwrap();
+        let mut out = Vec::new();
+        write_tree(&rows, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "aa (1) total 15, 2 children UNBALANCED\n  \
+             bb (2) total 11, 2 children UNBALANCED\n    \
+             dd (4) total 4\n    \
+             ee (5) total 5\n  \
+             cc (3) total 3\n"
+        );
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let programs = programs();
+        let rows = report(&Tower::new(&programs), "aa").unwrap();
+        let mut out = Vec::new();
+        write_csv(&rows[..2], &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "program,parent,depth,weight,total_weight,children,balanced\n\
+             aa,,0,1,15,2,false\n\
+             bb,aa,1,2,11,2,false\n"
+        );
+    }
+}
@@ -0,0 +1,268 @@
+// Checks that a list of programs describes exactly one well-formed tower, so the rest of the
+// solver can assume every program has a weight, at most one parent, and a path down to the root.
+
+use petgraph::algo::kosaraju_scc;
+use std::collections::HashMap;
+use std::fmt;
+
+use super::{Program, Tower};
+
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Problem {
+    // a program is given its own line more than once
+    DuplicateDefinition {
+        program: String,
+        line: usize,
+        first_line: usize,
+    },
+
+    // a program is held up by somebody but never given its own line (and so has no weight)
+    MissingWeight { program: String, line: usize },
+
+    // a program lists the same child more than once
+    RepeatedChild {
+        program: String,
+        child: String,
+        line: usize,
+    },
+
+    // a program is held up by more than one other program (lines of each of its parents)
+    MultipleParents { program: String, lines: Vec<usize> },
+
+    // more than one program isn't held up by anybody (each root and the line defining it)
+    MultipleRoots { roots: Vec<(String, usize)> },
+
+    // programs holding each other up in a loop (each program and the line defining it)
+    Cycle { programs: Vec<(String, usize)> },
+
+    NoPrograms,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Problem::DuplicateDefinition {
+                ref program,
+                line,
+                first_line,
+            } => write!(
+                f,
+                "line {}: \"{}\" was already defined on line {}",
+                line, program, first_line
+            ),
+            Problem::MissingWeight { ref program, line } => {
+                write!(f, "line {}: \"{}\" is never given a weight", line, program)
+            }
+            Problem::RepeatedChild {
+                ref program,
+                ref child,
+                line,
+            } => write!(f, "line {}: \"{}\" holds up \"{}\" more than once", line, program, child),
+            Problem::MultipleParents {
+                ref program,
+                ref lines,
+            } => write!(f, "{}: \"{}\" has more than one parent", describe_lines(lines), program),
+            Problem::MultipleRoots { ref roots } => {
+                let (names, lines) = describe(roots);
+                write!(f, "{}: there are several roots: {}", lines, names)
+            }
+            Problem::Cycle { ref programs } if programs.len() == 1 => {
+                let (names, lines) = describe(programs);
+                write!(f, "{}: {} holds itself up", lines, names)
+            }
+            Problem::Cycle { ref programs } => {
+                let (names, lines) = describe(programs);
+                write!(f, "{}: {} hold each other up in a loop", lines, names)
+            }
+            Problem::NoPrograms => write!(f, "there are no programs in the tower"),
+        }
+    }
+}
+
+// the quoted names and the lines of some programs, e.g. ("\"aa\", \"bb\"", "lines 1, 2")
+fn describe(programs: &[(String, usize)]) -> (String, String) {
+    let names: Vec<String> = programs.iter().map(|(name, _)| format!("\"{}\"", name)).collect();
+    let lines: Vec<usize> = programs.iter().map(|&(_, line)| line).collect();
+    (names.join(", "), describe_lines(&lines))
+}
+
+fn describe_lines(lines: &[usize]) -> String {
+    match lines {
+        [line] => format!("line {}", line),
+        _ => {
+            let lines: Vec<String> = lines.iter().map(ToString::to_string).collect();
+            format!("lines {}", lines.join(", "))
+        }
+    }
+}
+
+// every problem with the programs, roughly in the order of the lines they're on (an empty list
+// means they form a single tower)
+pub fn validate(programs: &[Program]) -> Vec<Problem> {
+    let mut problems = Vec::new();
+
+    if programs.is_empty() {
+        problems.push(Problem::NoPrograms);
+        return problems;
+    }
+
+    // the line each program is first defined on
+    let mut definitions: HashMap<&str, usize> = HashMap::new();
+    for program in programs.iter() {
+        match definitions.get(program.name.as_str()) {
+            Some(&first_line) => problems.push(Problem::DuplicateDefinition {
+                program: program.name.clone(),
+                line: program.line,
+                first_line,
+            }),
+            None => {
+                definitions.insert(&program.name, program.line);
+            }
+        }
+    }
+
+    // the lines of every parent holding up each program, in the order they appear
+    let mut parents: Vec<(&str, Vec<usize>)> = Vec::new();
+    let mut parent_index: HashMap<&str, usize> = HashMap::new();
+    for program in programs.iter() {
+        for child in program.children.iter() {
+            let index = *parent_index.entry(child).or_insert_with(|| {
+                parents.push((child, Vec::new()));
+                parents.len() - 1
+            });
+            // (a child listed twice by the same program is reported as such, rather than as
+            // having two parents on the same line)
+            let lines = &mut parents[index].1;
+            if lines.last() == Some(&program.line) {
+                let problem = Problem::RepeatedChild {
+                    program: program.name.clone(),
+                    child: child.clone(),
+                    line: program.line,
+                };
+                if !problems.contains(&problem) {
+                    problems.push(problem);
+                }
+            } else {
+                lines.push(program.line);
+            }
+        }
+    }
+
+    for &(child, ref lines) in parents.iter() {
+        if !definitions.contains_key(child) {
+            problems.push(Problem::MissingWeight {
+                program: child.to_string(),
+                line: lines[0],
+            });
+        }
+        if lines.len() > 1 {
+            problems.push(Problem::MultipleParents {
+                program: child.to_string(),
+                lines: lines.clone(),
+            });
+        }
+    }
+
+    // programs that nobody is holding up (a tower with a loop in it may have none at all, but
+    // then the loop gets reported below)
+    let roots: Vec<(String, usize)> = programs.iter()
+        .filter(|program| !parent_index.contains_key(program.name.as_str()))
+        .filter(|program| definitions[program.name.as_str()] == program.line)
+        .map(|program| (program.name.clone(), program.line))
+        .collect();
+    if roots.len() > 1 {
+        problems.push(Problem::MultipleRoots { roots });
+    }
+
+    // any strongly connected group of more than one program is a loop, as is a program holding
+    // itself up
+    let tower = Tower::new(programs);
+    let mut loops = Vec::new();
+    for component in kosaraju_scc(&tower.tree) {
+        let is_loop = component.len() > 1 || tower.tree.contains_edge(component[0], component[0]);
+        if is_loop {
+            let mut members: Vec<(String, usize)> = component.iter()
+                .map(|&name| (name.to_string(), definitions.get(name).cloned().unwrap_or(0)))
+                .collect();
+            members.sort_by_key(|&(_, line)| line);
+            loops.push(members);
+        }
+    }
+    loops.sort_by_key(|members| members[0].1);
+    problems.extend(loops.into_iter().map(|programs| Problem::Cycle { programs }));
+
+    problems
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Solution;
+    use day07::Day07;
+
+    fn problems(input: &str) -> Vec<String> {
+        validate(&Day07::parse(input).unwrap()).iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_valid() {
+        assert!(problems(include_str!("../../data/day07-test.txt")).is_empty());
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(problems(""), vec!["there are no programs in the tower"]);
+    }
+
+    #[test]
+    fn test_duplicate_and_missing() {
+        assert_eq!(
+            problems("aa (1) -> bb, cc\nbb (2)\nbb (3)\n"),
+            vec![
+                "line 3: \"bb\" was already defined on line 2",
+                "line 1: \"cc\" is never given a weight",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multiple_parents_and_roots() {
+        assert_eq!(
+            problems("aa (1) -> cc\nbb (1) -> cc\ncc (1)\n"),
+            vec![
+                "lines 1, 2: \"cc\" has more than one parent",
+                "lines 1, 2: there are several roots: \"aa\", \"bb\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repeated_child() {
+        assert_eq!(
+            problems("aa (1) -> bb, bb, bb\nbb (1)\n"),
+            vec!["line 1: \"aa\" holds up \"bb\" more than once"]
+        );
+        assert_eq!(
+            problems("aa (1) -> bb, bb\ncc (1) -> bb\nbb (1)\n"),
+            vec![
+                "line 1: \"aa\" holds up \"bb\" more than once",
+                "lines 1, 2: \"bb\" has more than one parent",
+                "lines 1, 2: there are several roots: \"aa\", \"cc\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cycle() {
+        assert_eq!(
+            problems("aa (1) -> bb\nbb (1) -> cc\ncc (1) -> bb\ndd (1) -> dd\n"),
+            vec![
+                "lines 1, 3: \"bb\" has more than one parent",
+                "lines 2, 3: \"bb\", \"cc\" hold each other up in a loop",
+                "line 4: \"dd\" holds itself up",
+            ]
+        );
+    }
+}
@@ -0,0 +1,141 @@
+// Errors any solver can return instead of panicking.
+
+use std::error;
+use std::fmt;
+use std::io;
+use std::result;
+use std::str::FromStr;
+
+
+pub type Result<T> = result::Result<T, Error>;
+
+
+#[derive(Debug)]
+pub enum Error {
+    // the puzzle input couldn't be read at all
+    Io(io::Error),
+
+    // part of the puzzle input couldn't be understood (line and column are one-indexed)
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+
+    // the input was understood, but doesn't describe a puzzle that has an answer
+    Unsolvable(String),
+
+    // the input was understood, but has several things wrong with it (one message each)
+    Invalid(Vec<String>),
+}
+
+impl Error {
+    pub fn parse<S: Into<String>>(line: usize, column: usize, message: S) -> Error {
+        Error::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn unsolvable<S: Into<String>>(message: S) -> Error {
+        Error::Unsolvable(message.into())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref e) => write!(f, "could not read input: {}", e),
+            Error::Parse {
+                line,
+                column,
+                ref message,
+            } => write!(f, "line {}, column {}: {}", line, column, message),
+            Error::Unsolvable(ref message) => write!(f, "{}", message),
+            Error::Invalid(ref problems) => {
+                match problems.len() {
+                    1 => write!(f, "found 1 problem in the input:")?,
+                    n => write!(f, "found {} problems in the input:", n)?,
+                }
+                for problem in problems.iter() {
+                    write!(f, "\n  {}", problem)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::Io(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
+
+// iterate over the whitespace-separated tokens of a line, along with the (one-indexed) column
+// each one starts at
+pub fn tokens(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    line.split_whitespace()
+        .map(move |token| (column_of(line, token), token))
+}
+
+// column of a substring within the line it was sliced from
+pub fn column_of(line: &str, token: &str) -> usize {
+    let offset = token.as_ptr() as usize - line.as_ptr() as usize;
+    line[..offset].chars().count() + 1
+}
+
+// parse a token found at the given position, describing what was expected if it isn't valid
+pub fn parse_token<T>(token: &str, line: usize, column: usize, expected: &str) -> Result<T>
+where
+    T: FromStr,
+{
+    token.parse().map_err(|_| {
+        let message = format!("expected {}, found \"{}\"", expected, abbreviate(token));
+        Error::parse(line, column, message)
+    })
+}
+
+// shorten a piece of input so it can be quoted in an error message
+pub fn abbreviate(token: &str) -> String {
+    const MAX_CHARS: usize = 20;
+    match token.char_indices().nth(MAX_CHARS) {
+        Some((end, _)) => format!("{}...", &token[..end]),
+        None => token.to_string(),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_columns() {
+        let found: Vec<(usize, &str)> = tokens("  12\t-3  x").collect();
+        assert_eq!(found, vec![(3, "12"), (6, "-3"), (10, "x")]);
+    }
+
+    #[test]
+    fn bad_token() {
+        let error = parse_token::<i32>("x", 2, 5, "a number").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 5: expected a number, found \"x\"");
+    }
+
+    #[test]
+    fn long_token() {
+        assert_eq!(abbreviate("12345678901234567890"), "12345678901234567890");
+        assert_eq!(abbreviate("123456789012345678901"), "12345678901234567890...");
+    }
+}
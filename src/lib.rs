@@ -0,0 +1,89 @@
+// Solutions to Advent of Code 2017, one module per day.
+//
+// Each day exposes a unit struct implementing `Solution`, so callers can parse a puzzle input once
+// and then ask for either part's answer without going through the `src/bin` executables.
+
+extern crate nom;
+extern crate petgraph;
+extern crate unicode_segmentation;
+
+use std::fmt::Display;
+use std::io::{self, Read};
+use std::process;
+
+pub mod error;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+
+pub use error::{Error, Result};
+
+
+// common interface shared by every day's solver
+pub trait Solution {
+    // parsed representation of the puzzle input, shared by both parts
+    type Input;
+
+    // answer types of the two parts (they aren't always the same, e.g. day 7)
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    // turn the raw puzzle input into the representation both parts work on
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne>;
+
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo>;
+}
+
+
+// number of days solved so far
+pub const DAYS: u32 = 7;
+
+
+// run one part of one day's solver against the raw puzzle input, returning the answer formatted
+// for display (or None if there's no solver for that day and part)
+pub fn solve(day: u32, part: u32, input: &str) -> Option<Result<String>> {
+    match day {
+        1 => solve_part::<day01::Day01>(part, input),
+        2 => solve_part::<day02::Day02>(part, input),
+        3 => solve_part::<day03::Day03>(part, input),
+        4 => solve_part::<day04::Day04>(part, input),
+        5 => solve_part::<day05::Day05>(part, input),
+        6 => solve_part::<day06::Day06>(part, input),
+        7 => solve_part::<day07::Day07>(part, input),
+        _ => None,
+    }
+}
+
+fn solve_part<S: Solution>(part: u32, input: &str) -> Option<Result<String>> {
+    match part {
+        1 => Some(S::parse(input).and_then(|i| S::part_one(&i)).map(|a| a.to_string())),
+        2 => Some(S::parse(input).and_then(|i| S::part_two(&i)).map(|a| a.to_string())),
+        _ => None,
+    }
+}
+
+
+// read all of stdin into a string (every binary takes its puzzle input this way)
+pub fn read_stdin() -> Result<String> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    Ok(input)
+}
+
+
+// print a binary's answer, or report why there isn't one and exit with a failure status
+pub fn print_answer<T: Display>(answer: Result<T>) {
+    match answer {
+        Ok(answer) => println!("{}", answer),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        }
+    }
+}
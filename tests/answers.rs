@@ -0,0 +1,77 @@
+// Runs every solver against every puzzle input in data/ and compares the results to the known
+// answers in data/answers.toml, so refactors can't silently change what the solvers compute.
+
+extern crate advent2017;
+extern crate toml;
+
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use toml::Value;
+
+
+fn data_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("data")
+}
+
+fn read_file(path: &Path) -> String {
+    let mut contents = String::new();
+    File::open(path)
+        .and_then(|mut file| file.read_to_string(&mut contents))
+        .unwrap_or_else(|e| panic!("could not read {}: {}", path.display(), e));
+    contents
+}
+
+// the parsed manifest: one table per input file
+fn answers() -> toml::value::Table {
+    match read_file(&data_dir().join("answers.toml")).parse::<Value>() {
+        Ok(Value::Table(table)) => table,
+        Ok(_) => panic!("answers.toml is not a table"),
+        Err(e) => panic!("could not parse answers.toml: {}", e),
+    }
+}
+
+
+#[test]
+fn known_answers() {
+    let mut failures = Vec::new();
+
+    for (file, entry) in answers().iter() {
+        let day = entry.get("day")
+            .and_then(Value::as_integer)
+            .unwrap_or_else(|| panic!("{} has no day", file)) as u32;
+        let input = read_file(&data_dir().join(file));
+
+        for (part, key) in [(1, "part_one"), (2, "part_two")].iter() {
+            let expected = match entry.get(*key) {
+                Some(expected) => expected.as_str()
+                    .unwrap_or_else(|| panic!("{} {} must be a string", file, key)),
+                None => continue,
+            };
+            match advent2017::solve(day, *part, &input) {
+                Some(Ok(ref actual)) if actual == expected => (),
+                actual => failures.push(format!(
+                    "{} (day {} part {}): expected {}, got {:?}",
+                    file, day, part, expected, actual
+                )),
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "wrong answers:\n{}", failures.join("\n"));
+}
+
+
+#[test]
+fn every_input_has_answers() {
+    let answers = answers();
+
+    let mut missing: Vec<String> = fs::read_dir(data_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .filter(|file| file.ends_with(".txt") && !answers.contains_key(file))
+        .collect();
+    missing.sort();
+
+    assert!(missing.is_empty(), "inputs missing from answers.toml: {:?}", missing);
+}